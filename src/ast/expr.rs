@@ -15,8 +15,9 @@ pub enum LiteralValue {
 pub struct FunctionValue {
 	pub keyword: Token,
 	pub name: Option<Token>,
-	pub params: Option<Rc<Vec<Token>>>,
+	pub params: Option<Rc<Vec<Parameter>>>,
 	pub body: Option<Rc<Vec<Stmt>>>,
+	pub doc: Option<Rc<str>>,
 }
 
 pub struct SetValue {
@@ -24,6 +25,7 @@ pub struct SetValue {
 	pub key: GetAccessor,
 	pub value: Box<Expr>,
 	pub blame: Token,
+	pub operator: Option<Token>,
 }
 
 pub struct SuperValue {
@@ -36,12 +38,21 @@ pub struct CallValue {
 	pub calee: Box<Expr>,
 	pub closing_paren: Token,
 	pub arguments: Vec<Expr>,
+	pub optional: bool,
 }
 
 pub struct AssignmentValue {
 	pub name: Token,
 	pub value: Box<Expr>,
 	pub env_distance: Cell<u32>,
+	pub operator: Option<Token>,
+}
+
+pub struct DestructuringAssignmentValue {
+	pub pattern: Pattern,
+	pub value: Box<Expr>,
+	pub targets: Vec<Expr>,
+	pub blame: Token,
 }
 
 pub struct BinaryValue {
@@ -54,6 +65,7 @@ pub struct GetValue {
 	pub getee: Box<Expr>,
 	pub key: GetAccessor,
 	pub blame: Token,
+	pub optional: bool,
 }
 
 pub struct IdentifierValue {
@@ -80,12 +92,43 @@ pub struct GroupingValue {
 	pub expression: Box<Expr>,
 }
 
+pub struct TemplateValue {
+	pub parts: Vec<Expr>,
+}
+
+pub struct ConditionalValue {
+	pub condition: Box<Expr>,
+	pub then: Box<Expr>,
+	pub otherwise: Option<Box<Expr>>,
+}
+
+pub struct SequenceValue {
+	pub statements: Vec<Stmt>,
+	pub value: Option<Box<Expr>>,
+}
+
+pub struct MatchValue {
+	pub keyword: Token,
+	pub value: Box<Expr>,
+	pub arms: Vec<MatchArm>,
+}
+
+pub struct SpreadValue {
+	pub blame: Token,
+	pub value: Box<Expr>,
+}
+
+pub struct OptionalChainValue {
+	pub expression: Box<Expr>,
+}
+
 pub enum Expr {
 	Function(FunctionValue),
 	Set(SetValue),
 	Super(SuperValue),
 	Call(CallValue),
 	Assignment(AssignmentValue),
+	DestructuringAssignment(DestructuringAssignmentValue),
 	Binary(BinaryValue),
 	Get(GetValue),
 	Identifier(IdentifierValue),
@@ -93,6 +136,12 @@ pub enum Expr {
 	This(ThisValue),
 	Unary(UnaryValue),
 	Grouping(GroupingValue),
+	Template(TemplateValue),
+	Conditional(ConditionalValue),
+	Sequence(SequenceValue),
+	Match(MatchValue),
+	Spread(SpreadValue),
+	OptionalChain(OptionalChainValue),
 	Literal(LiteralValue),
 }
 
@@ -101,9 +150,55 @@ pub enum GetAccessor {
 	DotEval(Box<Expr>),
 	SubscriptionNumber(f64),
 	SubscriptionEval(Box<Expr>),
+	/// `[start:end:step]`, every part being optional
+	Slice {
+		start: Option<Box<Expr>>,
+		end: Option<Box<Expr>>,
+		step: Option<Box<Expr>>,
+	},
 }
 
 pub enum SuperAccessor {
 	Method(Token),
 	Call(Vec<Expr>),
 }
+
+pub struct Parameter {
+	pub name: Token,
+	pub default: Option<Expr>,
+	pub rest: bool,
+}
+
+pub struct MatchArm {
+	pub pattern: Pattern,
+	pub guard: Option<Expr>,
+	pub body: Expr,
+}
+
+pub enum Pattern {
+	Literal(Box<Expr>),
+	Binding(Option<Token>),
+	Type {
+		kind: PatternType,
+		binding: Option<Token>,
+	},
+	List {
+		before: Vec<Pattern>,
+		rest: Option<Box<Pattern>>,
+		after: Vec<Pattern>,
+	},
+	Object {
+		class: Option<Box<Expr>>,
+		properties: Vec<PropertyPattern>,
+	},
+}
+
+pub enum PatternType {
+	Builtin(Rc<str>),
+	Class(Box<Expr>),
+}
+
+pub struct PropertyPattern {
+	pub key: Rc<str>,
+	pub pattern: Pattern,
+}
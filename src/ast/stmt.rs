@@ -1,10 +1,21 @@
-use crate::ast::expr::Expr;
+use crate::ast::expr::{Expr, Pattern};
 use crate::token::Token;
+use std::rc::Rc;
 
 pub struct ForValue {
 	pub condition: Option<Expr>,
 	pub body: Box<Stmt>,
 	pub closer: Option<Box<Stmt>>,
+	pub label: Option<Token>,
+}
+
+pub struct ForInValue {
+	pub key: Option<Token>,
+	pub value: Token,
+	pub iterable: Expr,
+	pub body: Option<Box<Stmt>>,
+	pub blame: Token,
+	pub label: Option<Token>,
 }
 
 pub struct IfValue {
@@ -17,12 +28,38 @@ pub struct DeclarationValue {
 	pub name: Token,
 	pub initializer: Option<Expr>,
 	pub mutable: bool,
+	pub doc: Option<Rc<str>>,
+}
+
+pub struct DestructureValue {
+	pub pattern: Pattern,
+	pub initializer: Expr,
+	pub mutable: bool,
+	pub blame: Token,
+	pub doc: Option<Rc<str>>,
 }
 
 pub struct ClassValue {
 	pub name: Token,
 	pub methods: Vec<Expr>,
+	pub static_methods: Vec<Expr>,
+	pub getters: Vec<Expr>,
+	pub setters: Vec<Expr>,
+	pub fields: Rc<Vec<Field>>,
 	pub superclass: Option<Expr>,
+	pub doc: Option<Rc<str>>,
+}
+
+pub struct EnumValue {
+	pub name: Token,
+	pub variants: Vec<Variant>,
+	pub doc: Option<Rc<str>>,
+}
+
+pub struct TryValue {
+	pub body: Option<Box<Stmt>>,
+	pub catch: Option<Catch>,
+	pub finally: Option<Box<Stmt>>,
 }
 
 pub struct ReturnValue {
@@ -30,6 +67,23 @@ pub struct ReturnValue {
 	pub expression: Option<Expr>,
 }
 
+pub struct ThrowValue {
+	pub keyword: Token,
+	pub expression: Expr,
+}
+
+pub struct ImportValue {
+	pub path: Rc<str>,
+	pub name: Token,
+	pub blame: Token,
+}
+
+pub struct ExportValue {
+	pub declaration: Box<Stmt>,
+	pub names: Vec<Token>,
+	pub keyword: Token,
+}
+
 pub struct ExpressionValue {
 	pub expression: Expr,
 }
@@ -40,6 +94,7 @@ pub struct BlockValue {
 
 pub struct ContinueValue {
 	pub keyword: Token,
+	pub label: Option<Token>,
 }
 
 pub struct PrintValue {
@@ -48,17 +103,43 @@ pub struct PrintValue {
 
 pub struct BreakValue {
 	pub keyword: Token,
+	pub label: Option<Token>,
 }
 
 pub enum Stmt {
 	For(ForValue),
+	ForIn(ForInValue),
 	If(IfValue),
 	Declaration(DeclarationValue),
+	Destructure(DestructureValue),
 	Class(ClassValue),
+	Enum(EnumValue),
+	Try(TryValue),
 	Return(ReturnValue),
+	Throw(ThrowValue),
+	Import(ImportValue),
+	Export(ExportValue),
 	Expression(ExpressionValue),
 	Block(BlockValue),
 	Continue(ContinueValue),
 	Print(PrintValue),
 	Break(BreakValue),
 }
+
+pub struct Field {
+	pub name: Token,
+	pub initializer: Option<Expr>,
+	pub is_static: bool,
+	pub doc: Option<Rc<str>>,
+}
+
+pub struct Catch {
+	pub name: Option<Token>,
+	pub body: Option<Box<Stmt>>,
+}
+
+pub struct Variant {
+	pub name: Token,
+	/// `None` for variants declared without parentheses
+	pub fields: Option<Vec<Token>>,
+}
@@ -4,11 +4,12 @@ use super::{
 };
 use crate::{
 	env::{DeclaredValue, EnvironmentBase, EnvironmentWrapper},
+	source::{FileId, SourceMap},
 	token::Token,
 	unwrap_scope_mut,
 };
 
-use std::{cell::RefCell, rc::Rc};
+use std::{cell::RefCell, collections::HashMap, path::PathBuf, rc::Rc};
 
 
 #[derive(Clone)]
@@ -21,8 +22,54 @@ pub struct InterpreterEnvironment(
 			>,
 		>,
 	>,
+	Rc<ProgramState>,
 );
 
+/// A string along with byte offsets of its chars
+pub type CharOffsets = (Rc<str>, Rc<[usize]>);
+
+/// State shared by all environments of a running program, including the ones
+/// of imported modules
+pub struct ProgramState {
+	/// files of the program, so that errors can tell where they come from
+	pub files: Rc<RefCell<SourceMap>>,
+	/// values of evaluated modules by their canonical paths
+	pub modules: RefCell<HashMap<PathBuf, InterpreterValue>>,
+	/// paths of the modules being evaluated, in the order of imports
+	pub importing: RefCell<Vec<PathBuf>>,
+	/// files starting with the `"use chars";` pragma
+	pub char_files: RefCell<Vec<FileId>>,
+	/// byte offsets of chars of the string last indexed by chars, so that
+	/// indexing the same string over and over doesn't rescan it every time
+	pub char_offsets: RefCell<Option<CharOffsets>>,
+}
+
+impl ProgramState {
+	/// Whether strings get indexed by chars rather than bytes in the file of
+	/// the token
+	pub fn uses_chars(&self, token: &Token) -> bool {
+		self.char_files.borrow().contains(&token.location.file)
+	}
+
+	/// Byte offsets of the chars of the string, reused as long as the same
+	/// string gets indexed
+	pub fn char_offsets(&self, s: &Rc<str>) -> Rc<[usize]> {
+		let mut cached = self.char_offsets.borrow_mut();
+
+		match &*cached {
+			Some((string, offsets)) if Rc::ptr_eq(string, s) => offsets.clone(),
+			_ => {
+				let offsets: Rc<[usize]> =
+					s.char_indices().map(|(offset, _)| offset).collect();
+
+				*cached = Some((s.clone(), offsets.clone()));
+
+				offsets
+			}
+		}
+	}
+}
+
 impl PartialEq for InterpreterEnvironment {
 	fn eq(&self, other: &Self) -> bool {
 		Rc::ptr_eq(&self.0, &other.0)
@@ -31,13 +78,14 @@ impl PartialEq for InterpreterEnvironment {
 
 impl EnvironmentWrapper<InterpreterValue> for InterpreterEnvironment {
 	fn new() -> Self {
-		Self(Rc::new(RefCell::new(EnvironmentBase::new(None))))
+		Self::with_files(Rc::default())
 	}
 
 	fn fork(&self) -> Self {
-		Self(Rc::new(RefCell::new(EnvironmentBase::new(Some(
-			self.clone(),
-		)))))
+		Self(
+			Rc::new(RefCell::new(EnvironmentBase::new(Some(self.clone())))),
+			self.1.clone(),
+		)
 	}
 
 	fn read(
@@ -113,14 +161,45 @@ impl EnvironmentWrapper<InterpreterValue> for InterpreterEnvironment {
 
 			Ok(value)
 		} else {
-			Err(RuntimeError {
-				message: format!(
+			Err(RuntimeError::new(
+				format!(
 					"Cannot reassign a const {} `{}`",
 					entry.value.human_type(),
 					name
 				),
-				token: identifier.clone(),
-			})
+				identifier.clone(),
+			))
 		}
 	}
 }
+
+impl InterpreterEnvironment {
+	pub fn with_files(files: Rc<RefCell<SourceMap>>) -> Self {
+		Self(
+			Rc::new(RefCell::new(EnvironmentBase::new(None))),
+			Rc::new(ProgramState {
+				files,
+				modules: RefCell::new(HashMap::new()),
+				importing: RefCell::new(Vec::new()),
+				char_files: RefCell::new(Vec::new()),
+				char_offsets: RefCell::new(None),
+			}),
+		)
+	}
+
+	/// A fresh top-level environment for a module, sharing the program state
+	pub fn module(&self) -> Self {
+		Self(
+			Rc::new(RefCell::new(EnvironmentBase::new(None))),
+			self.1.clone(),
+		)
+	}
+
+	pub fn program(&self) -> &ProgramState {
+		&self.1
+	}
+
+	pub fn files(&self) -> &Rc<RefCell<SourceMap>> {
+		&self.1.files
+	}
+}
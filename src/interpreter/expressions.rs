@@ -5,37 +5,60 @@ use super::{
 		bind_function,
 		confirm_arity,
 		construct_lox_defined_function,
+		eval_subscription,
+		extract_slice,
 		extract_subscription_index,
+		function_arity,
 		guard_function,
+		guard_sequence,
+		integer_to_number,
+		make_range,
 		map_arguments,
-		unwrap_list,
+		normalize_index,
+		number_to_integer,
+		range_len,
+		range_values,
 	},
 	interpret::{eval_expression, eval_statements},
-	types::{InterpreterFunction, InterpreterValue, RuntimeError},
+	statements::destructure,
+	types::{
+		EnumDeclaration,
+		InterpreterFunction,
+		InterpreterValue,
+		RuntimeError,
+	},
 };
 use crate::{
 	ast::expr::{
 		AssignmentValue,
 		BinaryValue,
 		CallValue,
+		ConditionalValue,
+		DestructuringAssignmentValue,
 		Expr,
 		FunctionValue,
 		GetAccessor,
 		GetValue,
 		IdentifierValue,
 		LiteralValue,
+		MatchValue,
 		ObjectValue,
+		OptionalChainValue,
+		Pattern,
+		PatternType,
+		SequenceValue,
 		SetValue,
 		SuperAccessor,
 		SuperValue,
+		TemplateValue,
 		ThisValue,
 		UnaryValue,
 	},
 	env::{DeclaredValue, EnvironmentWrapper},
-	token::{Location, Token, TokenType},
+	token::{Token, TokenType},
 };
 
-use std::{cell::RefCell, collections::HashMap, rc::Rc};
+use std::{cell::RefCell, collections::HashMap, convert::TryFrom, rc::Rc};
 
 
 // inlining because it's used only once, but i wanted to take it
@@ -53,16 +76,47 @@ pub fn literal_expression(
 		LiteralValue::Nil => Ok(InterpreterValue::Nil),
 		LiteralValue::Char(c) => Ok(InterpreterValue::Char(*c)),
 		LiteralValue::List(l) => {
-			let values =
-				l.iter()
-					.map(|expr| eval_expression(expr, env))
-					.collect::<Result<Vec<InterpreterValue>, RuntimeError>>()?;
+			let values = eval_arguments(l, env)?;
 
 			Ok(InterpreterValue::List(Rc::new(RefCell::new(values))))
 		}
 	}
 }
 
+/// Evaluates list elements or call arguments, expanding the spread ones
+pub fn eval_arguments(
+	arguments: &[Expr],
+	env: &InterpreterEnvironment,
+) -> Result<Vec<InterpreterValue>, RuntimeError> {
+	let mut values = Vec::with_capacity(arguments.len());
+
+	for argument in arguments {
+		if let Expr::Spread(v) = argument {
+			match eval_expression(&v.value, env)? {
+				InterpreterValue::List(l) => {
+					values.extend(l.borrow().iter().cloned());
+				}
+				InterpreterValue::String(s) => {
+					values.extend(s.chars().map(InterpreterValue::Char));
+				}
+				InterpreterValue::Range { start, end } => {
+					values.extend(range_values(start, end));
+				}
+				value => {
+					return Err(RuntimeError::new(
+						format!("Cannot spread {}", value.human_type()),
+						v.blame.clone(),
+					))
+				}
+			}
+		} else {
+			values.push(eval_expression(argument, env)?);
+		}
+	}
+
+	Ok(values)
+}
+
 #[inline]
 pub fn identifier_expression<E, T>(
 	v: &IdentifierValue,
@@ -74,20 +128,50 @@ where
 	Ok(env.read(v.env_distance.get(), &v.name)?.value)
 }
 
-#[inline]
-pub fn assignment_expression<E, T>(
-	expr_evaluator: fn(&Expr, &E) -> Result<T, RuntimeError>,
+pub fn assignment_expression(
 	v: &AssignmentValue,
-	env: &E,
-) -> Result<T, RuntimeError>
-where
-	E: EnvironmentWrapper<T>,
-{
-	env.assign(
-		v.env_distance.get(),
-		&v.name,
-		expr_evaluator(&v.value, env)?,
-	)
+	env: &InterpreterEnvironment,
+) -> Result<InterpreterValue, RuntimeError> {
+	let env_distance = v.env_distance.get();
+
+	let value = if let Some(operator) = &v.operator {
+		let current = env.read(env_distance, &v.name)?.value;
+
+		binary_operation(operator, &current, &eval_expression(&v.value, env)?)?
+	} else {
+		eval_expression(&v.value, env)?
+	};
+
+	env.assign(env_distance, &v.name, value)
+}
+
+pub fn destructuring_assignment_expression(
+	v: &DestructuringAssignmentValue,
+	env: &InterpreterEnvironment,
+) -> Result<InterpreterValue, RuntimeError> {
+	let value = eval_expression(&v.value, env)?;
+
+	destructure(&v.pattern, &value, &v.blame, env, &mut |binding, value| {
+		let name = assume_identifier(binding);
+
+		// every binding has a target of the same name, resolved by the resolver
+		let target = v
+			.targets
+			.iter()
+			.find_map(|target| match target {
+				Expr::Identifier(t) if assume_identifier(&t.name) == name => {
+					Some(t)
+				}
+				_ => None,
+			})
+			.expect("Destructuring target");
+
+		env.assign(target.env_distance.get(), binding, value)?;
+
+		Ok(())
+	})?;
+
+	Ok(value)
 }
 
 #[inline]
@@ -106,60 +190,139 @@ pub fn execute_call(
 	blame: &Token,
 	env: &InterpreterEnvironment,
 ) -> Result<InterpreterValue, RuntimeError> {
-	match callee {
-		InterpreterValue::Function { fun, enclosing_env } => {
-			let arguments = arguments
-				.iter()
-				.map(|arg| eval_expression(arg, env))
-				.collect::<Result<Vec<_>, RuntimeError>>()?;
+	let arguments = eval_arguments(arguments, env)?;
 
-			match &**fun {
-				InterpreterFunction::LoxDefined(fv) => {
-					confirm_arity(
-						fv.params.as_ref().map_or(0, |p| p.len()),
-						arguments.len(),
-						blame,
-					)?;
+	call_value(callee, &arguments, blame)
+}
 
-					let fun_env = &enclosing_env.fork();
+/// Calls a function or a class with already evaluated arguments
+pub fn call_value(
+	callee: &InterpreterValue,
+	arguments: &[InterpreterValue],
+	blame: &Token,
+) -> Result<InterpreterValue, RuntimeError> {
+	match callee {
+		InterpreterValue::Function { fun, enclosing_env } => match &**fun {
+			InterpreterFunction::LoxDefined(fv) => {
+				let (min, max) = fv
+					.params
+					.as_ref()
+					.map_or((0, Some(0)), |params| function_arity(params));
 
-					if let Some(params) = &fv.params {
-						map_arguments(params, &arguments, fun_env);
-					}
+				confirm_arity(min, max, arguments.len(), blame)?;
 
-					if let Some(statements) = &fv.body {
-						let e = eval_statements(&*statements, fun_env)?;
-						Ok(guard_function(e)?)
-					} else {
-						Ok(InterpreterValue::Nil)
-					}
+				let fun_env = &enclosing_env.fork();
+
+				if let Some(params) = &fv.params {
+					map_arguments(params, arguments, fun_env)?;
 				}
-				InterpreterFunction::Native { arity, fun } => {
-					confirm_arity(*arity, arguments.len(), blame)?;
 
-					Ok(fun(blame, &enclosing_env.fork(), &arguments)?)
+				if let Some(statements) = &fv.body {
+					let e = eval_statements(&*statements, fun_env)?;
+					Ok(guard_function(e)?)
+				} else {
+					Ok(InterpreterValue::Nil)
 				}
 			}
-		}
+			InterpreterFunction::Native { arity, fun } => {
+				confirm_arity(*arity, Some(*arity), arguments.len(), blame)?;
+
+				Ok(fun(blame, &enclosing_env.fork(), arguments)?)
+			}
+			InterpreterFunction::VariantConstructor { enumeration, index } => {
+				let arity = enumeration.variants[*index]
+					.fields
+					.as_ref()
+					.map_or(0, Vec::len);
+
+				confirm_arity(arity, Some(arity), arguments.len(), blame)?;
+
+				Ok(InterpreterValue::Variant {
+					enumeration: Rc::clone(enumeration),
+					index: *index,
+					values: Rc::new(arguments.to_vec()),
+				})
+			}
+		},
 		InterpreterValue::Class { constructor, .. } => {
 			let instance = InterpreterValue::Instance {
 				class: Some(Rc::new(callee.clone())),
 				properties: Rc::new(RefCell::new(HashMap::new())),
 			};
 
+			initialize_fields(callee, &instance)?;
+
 			if let Some(constructor) = &constructor {
 				let constructor = bind_function(constructor, instance.clone());
 
-				execute_call(&constructor, arguments, blame, env)?;
+				call_value(&constructor, arguments, blame)?;
 			}
 
 			Ok(instance)
 		}
-		_ => Err(RuntimeError {
-			message: format!("Cannot call {}", callee.human_type()),
-			token: blame.clone(),
-		}),
+		_ => Err(RuntimeError::new(
+			format!("Cannot call {}", callee.human_type()),
+			blame.clone(),
+		)),
+	}
+}
+
+/// Sets the declared instance fields, starting with the ones of the
+/// outermost superclass
+fn initialize_fields(
+	class: &InterpreterValue,
+	instance: &InterpreterValue,
+) -> Result<(), RuntimeError> {
+	let (superclass, members) = if let InterpreterValue::Class {
+		superclass,
+		members,
+		..
+	} = class
+	{
+		(superclass, members)
+	} else {
+		unreachable!("Class is not a class? \u{1f914}")
+	};
+
+	if let Some(superclass) = superclass {
+		initialize_fields(superclass, instance)?;
+	}
+
+	if members.fields.iter().all(|field| field.is_static) {
+		return Ok(());
+	}
+
+	let properties =
+		if let InterpreterValue::Instance { properties, .. } = instance {
+			properties
+		} else {
+			unreachable!("Instance is not an instance? \u{1f914}")
+		};
+
+	let field_env = members.env.fork();
+
+	field_env.declare(
+		"this".into(),
+		DeclaredValue {
+			mutable: false,
+			value: instance.clone(),
+		},
+	);
+
+	for field in members.fields.iter().filter(|field| !field.is_static) {
+		let value = field
+			.initializer
+			.as_ref()
+			.map_or(Ok(InterpreterValue::Nil), |initializer| {
+				eval_expression(initializer, &field_env)
+			})?;
+
+		properties
+			.borrow_mut()
+			.insert(assume_identifier(&field.name).to_owned(), value);
 	}
+
+	Ok(())
 }
 
 #[inline]
@@ -194,6 +357,9 @@ pub fn unary_expression(
 		(TokenType::Minus, InterpreterValue::Number(n)) => {
 			Ok(InterpreterValue::Number(-n))
 		}
+		(TokenType::Tilde, InterpreterValue::Number(n)) => {
+			integer_to_number(!number_to_integer(*n, &v.operator)?, &v.operator)
+		}
 		(TokenType::Bang, InterpreterValue::True) => {
 			Ok(InterpreterValue::False)
 		}
@@ -201,13 +367,13 @@ pub fn unary_expression(
 			Ok(InterpreterValue::True)
 		}
 
-		_ => Err(RuntimeError {
-			message: format!(
+		_ => Err(RuntimeError::new(
+			format!(
 				"Cannot use `{}` on `{}`",
 				v.operator.token_type, right_value
 			),
-			token: v.operator.clone(),
-		}),
+			v.operator.clone(),
+		)),
 	}
 }
 
@@ -235,6 +401,15 @@ pub fn binary_experssion(
 				left_value
 			});
 		}
+		TokenType::QuestionQuestion => {
+			let left_value = eval_expression(&v.left, env)?;
+
+			return if left_value == InterpreterValue::Nil {
+				eval_expression(&v.right, env)
+			} else {
+				Ok(left_value)
+			};
+		}
 		_ => (),
 	}
 
@@ -242,16 +417,25 @@ pub fn binary_experssion(
 	let left_value = eval_expression(&v.left, env)?;
 	let right_value = eval_expression(&v.right, env)?;
 
+	binary_operation(&v.operator, &left_value, &right_value)
+}
+
+/// Applies a non-logical binary operator, also used by compound assignments
+pub fn binary_operation(
+	operator: &Token,
+	left_value: &InterpreterValue,
+	right_value: &InterpreterValue,
+) -> Result<InterpreterValue, RuntimeError> {
 	// im sorry for this, but i found that the nested matches require
 	// much simpler patterns,
 	// and with this, i can achieve less comparisons overall
-	match v.operator.token_type {
+	match operator.token_type {
 		TokenType::BangEqual => Ok((left_value != right_value).into()),
 		TokenType::EqualEqual => Ok((left_value == right_value).into()),
 
-		_ => match (&left_value, &right_value) {
+		_ => match (left_value, right_value) {
 			(InterpreterValue::Number(n1), InterpreterValue::Number(n2)) => {
-				match v.operator.token_type {
+				match operator.token_type {
 					TokenType::Minus => Ok(InterpreterValue::Number(n1 - n2)),
 					TokenType::Slash => Ok(InterpreterValue::Number(n1 / n2)),
 					TokenType::Star => Ok(InterpreterValue::Number(n1 * n2)),
@@ -261,39 +445,172 @@ pub fn binary_experssion(
 					TokenType::Less => Ok((n1 < n2).into()),
 					TokenType::LessEqual => Ok((n1 <= n2).into()),
 					TokenType::Modulo => Ok(InterpreterValue::Number(n1 % n2)),
+					TokenType::StarStar => {
+						Ok(InterpreterValue::Number(n1.powf(*n2)))
+					}
+					TokenType::TildeSlash => {
+						if *n2 == 0.0 {
+							return Err(RuntimeError::new(
+								"Integer division by zero".into(),
+								operator.clone(),
+							));
+						}
+
+						Ok(InterpreterValue::Number((n1 / n2).trunc()))
+					}
+					TokenType::Ampersand
+					| TokenType::Pipe
+					| TokenType::Caret
+					| TokenType::LessLess
+					| TokenType::GreaterGreater => bitwise_operation(operator, *n1, *n2),
+					TokenType::DotDot | TokenType::DotDotEqual => {
+						make_range(operator, *n1, *n2)
+					}
 
 					_ => unreachable!("Scanner did a bad job \u{1f60e}."),
 				}
 			}
 			(InterpreterValue::String(s1), InterpreterValue::String(s2)) => {
-				if v.operator.token_type == TokenType::Plus {
-					Ok(InterpreterValue::String(Rc::from(s1.to_string() + s2)))
-				} else {
-					Err(RuntimeError {
-						message: format!(
+				match operator.token_type {
+					TokenType::Plus => Ok(InterpreterValue::String(Rc::from(
+						s1.to_string() + s2,
+					))),
+					// lexicographic, by code points
+					TokenType::Greater => Ok((s1 > s2).into()),
+					TokenType::GreaterEqual => Ok((s1 >= s2).into()),
+					TokenType::Less => Ok((s1 < s2).into()),
+					TokenType::LessEqual => Ok((s1 <= s2).into()),
+
+					_ => Err(RuntimeError::new(
+						format!(
 							"You cannot use `{}` on two strings. Did you mean \
 							 `+`?",
-							v.operator.token_type
+							operator.token_type
 						),
-						token: v.operator.clone(),
-					})
+						operator.clone(),
+					)),
+				}
+			}
+			(InterpreterValue::Char(c1), InterpreterValue::Char(c2)) => {
+				match operator.token_type {
+					TokenType::Greater => Ok((c1 > c2).into()),
+					TokenType::GreaterEqual => Ok((c1 >= c2).into()),
+					TokenType::Less => Ok((c1 < c2).into()),
+					TokenType::LessEqual => Ok((c1 <= c2).into()),
+					// the distance between code points
+					TokenType::Minus => Ok(InterpreterValue::Number(
+						f64::from(u32::from(*c1)) - f64::from(u32::from(*c2)),
+					)),
+
+					_ => Err(unsupported_operands(
+						operator,
+						left_value,
+						right_value,
+					)),
 				}
 			}
+			(InterpreterValue::Char(c), InterpreterValue::Number(n))
+				if matches!(
+					operator.token_type,
+					TokenType::Plus | TokenType::Minus
+				) =>
+			{
+				shift_char(operator, *c, *n)
+			}
+			(InterpreterValue::Number(n), InterpreterValue::Char(c))
+				if operator.token_type == TokenType::Plus =>
+			{
+				shift_char(operator, *c, *n)
+			}
 			// error bby
-			_ => Err(RuntimeError {
-				message: format!(
-					"Cannot use `{}` on {} and {}",
-					v.operator.token_type,
-					left_value.human_type(),
-					right_value.human_type()
-				),
-				token: v.operator.clone(),
-			}),
+			_ => Err(unsupported_operands(operator, left_value, right_value)),
 		},
 	}
 }
 
-fn find_method(
+fn unsupported_operands(
+	operator: &Token,
+	left_value: &InterpreterValue,
+	right_value: &InterpreterValue,
+) -> RuntimeError {
+	RuntimeError::new(
+		format!(
+			"Cannot use `{}` on {} and {}",
+			operator.token_type,
+			left_value.human_type(),
+			right_value.human_type()
+		),
+		operator.clone(),
+	)
+}
+
+/// Moves a char's code point by a number, for `char + number` and
+/// `char - number`
+fn shift_char(
+	operator: &Token,
+	c: char,
+	n: f64,
+) -> Result<InterpreterValue, RuntimeError> {
+	let offset = number_to_integer(n, operator)?;
+
+	let code_point = i64::from(u32::from(c));
+
+	let shifted = if operator.token_type == TokenType::Minus {
+		code_point.checked_sub(offset)
+	} else {
+		code_point.checked_add(offset)
+	};
+
+	shifted
+		.and_then(|shifted| u32::try_from(shifted).ok())
+		.and_then(char::from_u32)
+		.map(InterpreterValue::Char)
+		.ok_or_else(|| {
+			RuntimeError::new(
+				format!(
+					"Result of `{}` is not a valid char",
+					operator.token_type
+				),
+				operator.clone(),
+			)
+		})
+}
+
+fn bitwise_operation(
+	operator: &Token,
+	n1: f64,
+	n2: f64,
+) -> Result<InterpreterValue, RuntimeError> {
+	let i1 = number_to_integer(n1, operator)?;
+	let i2 = number_to_integer(n2, operator)?;
+
+	let result = match operator.token_type {
+		TokenType::Ampersand => i1 & i2,
+		TokenType::Pipe => i1 | i2,
+		TokenType::Caret => i1 ^ i2,
+		_ => {
+			let shift = u32::try_from(i2)
+				.ok()
+				.filter(|shift| *shift < i64::BITS)
+				.ok_or_else(|| {
+					RuntimeError::new(
+						format!("Cannot shift by {}", i2),
+						operator.clone(),
+					)
+				})?;
+
+			if operator.token_type == TokenType::LessLess {
+				i1 << shift
+			} else {
+				i1 >> shift
+			}
+		}
+	};
+
+	integer_to_number(result, operator)
+}
+
+pub fn find_method(
 	key: &str,
 	class: &InterpreterValue,
 	instance: &InterpreterValue,
@@ -315,79 +632,253 @@ fn find_method(
 	} else if let Some(superclass) = &superclass {
 		find_method(key, superclass, instance, blame)
 	} else {
-		Err(RuntimeError {
-			message: format!(
-				"Couldnt find property nor method with key {}",
-				key
-			),
-			token: blame.clone(),
-		})
+		Err(RuntimeError::new(
+			format!("Couldnt find property nor method with key {}", key),
+			blame.clone(),
+		))
 	}
 }
 
 fn get_dot(
 	v: &GetValue,
+	getee: &InterpreterValue,
 	env: &InterpreterEnvironment,
 ) -> Result<InterpreterValue, RuntimeError> {
-	// auxiliary function used only once down below, that's why inlining is
-	// completely justified 🥺
-	#[inline]
-	fn get_property(
-		key: &str,
-		properties: &HashMap<String, InterpreterValue>,
-		class: &Option<Rc<InterpreterValue>>,
-		instance: &InterpreterValue,
-		blame: &Token,
-	) -> Result<InterpreterValue, RuntimeError> {
-		if let Some(p) = properties.get(key) {
-			Ok(p.clone())
-		} else if let Some(class) = class {
-			find_method(key, class, instance, blame)
-		} else {
-			Err(RuntimeError {
-				message: format!("Property {} not defined", key),
-				token: blame.clone(),
+	if !matches!(
+		getee,
+		InterpreterValue::Instance { .. }
+			| InterpreterValue::Class { .. }
+			| InterpreterValue::Enum(_)
+			| InterpreterValue::Module(_)
+			| InterpreterValue::Variant { .. }
+	) {
+		return Err(RuntimeError::new(
+			format!("Can't access properties on {}", getee.human_type()),
+			v.blame.clone(),
+		));
+	}
+
+	let key = match &v.key {
+		GetAccessor::DotName(iden) => iden.to_string(),
+		GetAccessor::DotEval(expr) => eval_expression(expr, env)?.to_string(),
+		_ => unreachable!("Wrong accessor in dot"),
+	};
+
+	match getee {
+		InterpreterValue::Enum(enumeration) => {
+			get_variant(enumeration, &key, &v.blame, env)
+		}
+		InterpreterValue::Module(exports) => {
+			exports.get(&key).cloned().ok_or_else(|| {
+				RuntimeError::new(
+					format!("Module doesn't export {}", key),
+					v.blame.clone(),
+				)
 			})
 		}
+		InterpreterValue::Variant { .. } => variant_field(getee, &key)
+			.ok_or_else(|| {
+				RuntimeError::new(
+					format!("{} has no field {}", getee, key),
+					v.blame.clone(),
+				)
+			}),
+		_ => get_property(getee, &key, &v.blame),
 	}
+}
 
-	let getee = eval_expression(&v.getee, env)?;
+/// Gets a variant of an enum, which is either the constructor of the variant
+/// or, for variants without fields, the value itself
+fn get_variant(
+	enumeration: &Rc<EnumDeclaration>,
+	key: &str,
+	blame: &Token,
+	env: &InterpreterEnvironment,
+) -> Result<InterpreterValue, RuntimeError> {
+	let index = enumeration.variant_index(key).ok_or_else(|| {
+		RuntimeError::new(
+			format!("Enum {} has no variant {}", enumeration.name, key),
+			blame.clone(),
+		)
+	})?;
+
+	if enumeration.variants[index].fields.is_some() {
+		Ok(InterpreterValue::Function {
+			fun: Rc::new(InterpreterFunction::VariantConstructor {
+				enumeration: Rc::clone(enumeration),
+				index,
+			}),
+			enclosing_env: env.clone(),
+		})
+	} else {
+		Ok(InterpreterValue::Variant {
+			enumeration: Rc::clone(enumeration),
+			index,
+			values: Rc::default(),
+		})
+	}
+}
 
-	let (properties, class) =
-		if let InterpreterValue::Instance { properties, class } = &getee {
-			(properties, class)
-		} else {
-			return Err(RuntimeError {
-				message: format!(
-					"Can't access properties on {}",
-					getee.human_type()
-				),
-				token: v.blame.clone(),
-			});
-		};
+/// Reads a field of a variant by its declared name
+fn variant_field(
+	variant: &InterpreterValue,
+	key: &str,
+) -> Option<InterpreterValue> {
+	if let InterpreterValue::Variant {
+		enumeration,
+		index,
+		values,
+	} = variant
+	{
+		let fields = enumeration.variants[*index].fields.as_ref()?;
 
-	let borrowed_props = properties.borrow();
+		fields
+			.iter()
+			.position(|field| &**field == key)
+			.map(|position| values[position].clone())
+	} else {
+		unreachable!("Variant is not a variant? \u{1f914}")
+	}
+}
 
-	match &v.key {
-		GetAccessor::DotName(iden) => {
-			get_property(iden, &borrowed_props, class, &getee, &v.blame)
-		}
-		GetAccessor::DotEval(expr) => {
-			let key = eval_expression(expr, env)?.to_string();
+/// Reads a property of an instance (going through getters and methods), or a
+/// static member of a class
+fn get_property(
+	getee: &InterpreterValue,
+	key: &str,
+	blame: &Token,
+) -> Result<InterpreterValue, RuntimeError> {
+	match getee {
+		InterpreterValue::Instance { properties, class } => {
+			// the borrow has to end before calling a getter, which can modify
+			// the properties
+			let property = properties.borrow().get(key).cloned();
+
+			if let Some(property) = property {
+				return Ok(property);
+			}
+
+			let class = class.as_ref().ok_or_else(|| {
+				RuntimeError::new(
+					format!("Property {} not defined", key),
+					blame.clone(),
+				)
+			})?;
 
-			get_property(key.as_str(), &borrowed_props, class, &getee, &v.blame)
+			if let Some(getter) = find_accessor(key, class, false) {
+				return call_value(
+					&bind_function(&getter, getee.clone()),
+					&[],
+					blame,
+				);
+			}
+
+			find_method(key, class, getee, blame)
 		}
-		_ => unreachable!("Wrong accessor in dot"),
+		InterpreterValue::Class { .. } => find_static(key, getee, getee, blame),
+		_ => unreachable!("Only instances and classes have properties"),
+	}
+}
+
+/// Looks for a getter (or a setter) through the class and its superclasses
+fn find_accessor(
+	key: &str,
+	class: &InterpreterValue,
+	setter: bool,
+) -> Option<InterpreterValue> {
+	let (members, superclass) = if let InterpreterValue::Class {
+		members,
+		superclass,
+		..
+	} = class
+	{
+		(members, superclass)
+	} else {
+		unreachable!("Class is not a class? \u{1f914}")
+	};
+
+	let accessors = if setter {
+		&members.setters
+	} else {
+		&members.getters
+	};
+
+	accessors.get(key).cloned().or_else(|| {
+		superclass
+			.as_ref()
+			.and_then(|superclass| find_accessor(key, superclass, setter))
+	})
+}
+
+/// Looks for a static field or method through the class and its
+/// superclasses, static methods are bound to the class they were accessed on
+fn find_static(
+	key: &str,
+	class: &InterpreterValue,
+	accessed_class: &InterpreterValue,
+	blame: &Token,
+) -> Result<InterpreterValue, RuntimeError> {
+	let (members, superclass, name) = if let InterpreterValue::Class {
+		members,
+		superclass,
+		name,
+		..
+	} = class
+	{
+		(members, superclass, name)
+	} else {
+		unreachable!("Class is not a class? \u{1f914}")
+	};
+
+	let field = members.static_fields.borrow().get(key).cloned();
+
+	if let Some(field) = field {
+		return Ok(field);
+	}
+
+	if let Some(method) = members.static_methods.get(key) {
+		return Ok(bind_function(method, accessed_class.clone()));
 	}
+
+	if let Some(superclass) = superclass {
+		return find_static(key, superclass, accessed_class, blame);
+	}
+
+	Err(RuntimeError::new(
+		format!(
+			"Couldnt find static field nor method with key {} on class {}",
+			key, name
+		),
+		blame.clone(),
+	))
 }
 
 fn get_subscription(
 	v: &GetValue,
+	getee_val: InterpreterValue,
 	env: &InterpreterEnvironment,
 ) -> Result<InterpreterValue, RuntimeError> {
-	let getee_val = eval_expression(&v.getee, env)?;
+	if matches!(v.key, GetAccessor::Slice { .. }) {
+		return get_slice(v, getee_val, env);
+	}
 
 	match getee_val {
+		InterpreterValue::String(s) if env.program().uses_chars(&v.blame) => {
+			let offsets = env.program().char_offsets(&s);
+
+			let index = extract_subscription_index(
+				&v.key,
+				&v.blame,
+				offsets.len(),
+				env,
+			)?;
+
+			Ok(InterpreterValue::Char(
+				s[offsets[index]..].chars().next().unwrap_or_else(|| {
+					unreachable!("Offset is not of a char \u{1f914}")
+				}),
+			))
+		}
 		InterpreterValue::String(s) => {
 			let index =
 				extract_subscription_index(&v.key, &v.blame, s.len(), env)?;
@@ -399,21 +890,106 @@ fn get_subscription(
 			}
 		}
 		InterpreterValue::List(l) => {
+			// the list can't stay borrowed while evaluating the index, as it
+			// may use the list as well
+			let n = eval_subscription(&v.key, &v.blame, env)?;
+
 			let l_borrow = l.borrow();
 
+			let index = normalize_index(n, &v.blame, l_borrow.len())?;
+
+			unsafe { Ok(l_borrow.get_unchecked(index).clone()) }
+		}
+		InterpreterValue::Range { start, end } => {
 			let index = extract_subscription_index(
 				&v.key,
 				&v.blame,
-				l_borrow.len(),
+				range_len(start, end),
 				env,
 			)?;
 
-			unsafe { Ok(l_borrow.get_unchecked(index).clone()) }
+			// ranges are lazy, so the element is computed instead of stepping
+			// through the ones before it
+			let value = i64::try_from(index)
+				.ok()
+				.and_then(|index| start.checked_add(index))
+				.ok_or_else(|| {
+					RuntimeError::new(
+						format!("Index {} out of bounds", index),
+						v.blame.clone(),
+					)
+				})?;
+
+			integer_to_number(value, &v.blame)
+		}
+		_ => Err(RuntimeError::new(
+			format!("Cannot index {}", getee_val.human_type()),
+			v.blame.clone(),
+		)),
+	}
+}
+
+/// Copies the elements selected by a slice into a new list or string
+fn get_slice(
+	v: &GetValue,
+	getee_val: InterpreterValue,
+	env: &InterpreterEnvironment,
+) -> Result<InterpreterValue, RuntimeError> {
+	match getee_val {
+		InterpreterValue::String(s) if env.program().uses_chars(&v.blame) => {
+			let chars: Vec<char> = s.chars().collect();
+
+			let slice = extract_slice(&v.key, &v.blame, chars.len(), env)?;
+
+			Ok(InterpreterValue::String(
+				slice
+					.indices
+					.iter()
+					.map(|i| chars[*i])
+					.collect::<String>()
+					.into(),
+			))
+		}
+		InterpreterValue::String(s) => {
+			let bytes = s.as_bytes();
+
+			let slice = extract_slice(&v.key, &v.blame, bytes.len(), env)?;
+
+			let sliced = slice.indices.iter().map(|i| bytes[*i]).collect();
+
+			// the selected bytes have to make up whole characters
+			String::from_utf8(sliced)
+				.map(|sliced| InterpreterValue::String(sliced.into()))
+				.map_err(|_| {
+					RuntimeError::new(
+						"Slice cuts through a multi-byte character, use the \
+						 \"use chars\" pragma to slice by chars"
+							.into(),
+						v.blame.clone(),
+					)
+				})
+		}
+		InterpreterValue::List(l) => {
+			// the list can't stay borrowed while evaluating the bounds, as they
+			// may use the list as well
+			let len = l.borrow().len();
+
+			let slice = extract_slice(&v.key, &v.blame, len, env)?;
+
+			let l_borrow = l.borrow();
+
+			Ok(InterpreterValue::List(Rc::new(RefCell::new(
+				slice
+					.indices
+					.iter()
+					.filter_map(|i| l_borrow.get(*i).cloned())
+					.collect(),
+			))))
 		}
-		_ => Err(RuntimeError {
-			message: format!("Cannot index {}", getee_val.human_type()),
-			token: v.blame.clone(),
-		}),
+		_ => Err(RuntimeError::new(
+			format!("Cannot slice {}", getee_val.human_type()),
+			v.blame.clone(),
+		)),
 	}
 }
 
@@ -421,11 +997,67 @@ fn get_subscription(
 pub fn get_expression(
 	v: &GetValue,
 	env: &InterpreterEnvironment,
+) -> Result<InterpreterValue, RuntimeError> {
+	let getee = eval_expression(&v.getee, env)?;
+
+	get_value(v, getee, env)
+}
+
+fn get_value(
+	v: &GetValue,
+	getee: InterpreterValue,
+	env: &InterpreterEnvironment,
 ) -> Result<InterpreterValue, RuntimeError> {
 	if matches!(v.key, GetAccessor::DotName(_) | GetAccessor::DotEval(_)) {
-		get_dot(v, env)
+		get_dot(v, &getee, env)
 	} else {
-		get_subscription(v, env)
+		get_subscription(v, getee, env)
+	}
+}
+
+#[inline]
+pub fn optional_chain_expression(
+	v: &OptionalChainValue,
+	env: &InterpreterEnvironment,
+) -> Result<InterpreterValue, RuntimeError> {
+	Ok(eval_chain_link(&v.expression, env)?.unwrap_or(InterpreterValue::Nil))
+}
+
+/// Evaluates a link of an optional chain, `None` meaning that one of the
+/// optional links hit `nil` and the rest of the chain got skipped
+fn eval_chain_link(
+	expr: &Expr,
+	env: &InterpreterEnvironment,
+) -> Result<Option<InterpreterValue>, RuntimeError> {
+	match expr {
+		Expr::Get(v) => {
+			let getee = if let Some(getee) = eval_chain_link(&v.getee, env)? {
+				getee
+			} else {
+				return Ok(None);
+			};
+
+			// short-circuit before evaluating the key
+			if v.optional && getee == InterpreterValue::Nil {
+				return Ok(None);
+			}
+
+			get_value(v, getee, env).map(Some)
+		}
+		Expr::Call(v) => {
+			let callee = if let Some(callee) = eval_chain_link(&v.calee, env)? {
+				callee
+			} else {
+				return Ok(None);
+			};
+
+			if v.optional && callee == InterpreterValue::Nil {
+				return Ok(None);
+			}
+
+			execute_call(&callee, &v.arguments, &v.closing_paren, env).map(Some)
+		}
+		_ => eval_expression(expr, env).map(Some),
 	}
 }
 
@@ -435,58 +1067,200 @@ fn set_dot(
 ) -> Result<InterpreterValue, RuntimeError> {
 	let setee = eval_expression(&v.setee, env)?;
 
-	let properties = if let InterpreterValue::Instance { properties, .. } =
-		setee
-	{
-		properties
-	} else {
-		return Err(RuntimeError {
-			message: format!("Can't set properties on {}", setee.human_type()),
-			token: v.blame.clone(),
-		});
+	if !matches!(
+		setee,
+		InterpreterValue::Instance { .. } | InterpreterValue::Class { .. }
+	) {
+		return Err(RuntimeError::new(
+			format!("Can't set properties on {}", setee.human_type()),
+			v.blame.clone(),
+		));
+	}
+
+	let key = match &v.key {
+		GetAccessor::DotName(key) => key.to_string(),
+		GetAccessor::DotEval(expr) => eval_expression(expr, env)?.to_string(),
+		_ => unreachable!("How"),
 	};
 
-	let value = eval_expression(&v.value, env)?;
+	let value = if let Some(operator) = &v.operator {
+		let current = get_property(&setee, &key, &v.blame)?;
 
-	let mut borrowed_props = properties.borrow_mut();
+		binary_operation(operator, &current, &eval_expression(&v.value, env)?)?
+	} else {
+		eval_expression(&v.value, env)?
+	};
 
-	match &v.key {
-		GetAccessor::DotName(key) => {
-			borrowed_props.insert(key.to_string(), value.clone());
+	match &setee {
+		InterpreterValue::Instance { properties, class } => {
+			let setter = class
+				.as_ref()
+				.and_then(|class| find_accessor(&key, class, true));
+
+			if let Some(setter) = setter {
+				call_value(
+					&bind_function(&setter, setee.clone()),
+					std::slice::from_ref(&value),
+					&v.blame,
+				)?;
+			} else if class
+				.as_ref()
+				.and_then(|class| find_accessor(&key, class, false))
+				.is_some()
+			{
+				return Err(RuntimeError::new(
+					format!(
+						"Cannot set property {}, which only has a getter",
+						key
+					),
+					v.blame.clone(),
+				));
+			} else {
+				properties.borrow_mut().insert(key, value.clone());
+			}
 		}
-		GetAccessor::DotEval(expr) => {
-			let key = eval_expression(expr, env)?.to_string();
-			borrowed_props.insert(key, value.clone());
+		InterpreterValue::Class { .. } => {
+			set_static(key, value.clone(), &setee);
 		}
-		_ => unreachable!("How"),
-	};
+		_ => unreachable!("Only instances and classes have properties"),
+	}
 
 	Ok(value)
 }
 
+/// Sets the static field on the class itself, so that assigning through a
+/// subclass shadows the superclass's field instead of overwriting it
+fn set_static(key: String, value: InterpreterValue, class: &InterpreterValue) {
+	if let InterpreterValue::Class { members, .. } = class {
+		members.static_fields.borrow_mut().insert(key, value);
+	}
+}
+
 fn set_subscription(
 	v: &SetValue,
 	env: &InterpreterEnvironment,
 ) -> Result<InterpreterValue, RuntimeError> {
 	let setee = eval_expression(&v.setee, env)?;
 
-	let mut l_borrow = unwrap_list(
-		&setee,
-		&v.blame,
-		0,
-		Some(
+	let list = if let InterpreterValue::List(l) = &setee {
+		l
+	} else {
+		return Err(RuntimeError::new(
 			"Setting values by using the `[]` operator is allowed only on \
 			 lists"
 				.to_owned(),
-		),
-	)?;
+			v.blame.clone(),
+		));
+	};
 
-	let index =
-		extract_subscription_index(&v.key, &v.blame, l_borrow.len(), env)?;
+	if matches!(v.key, GetAccessor::Slice { .. }) {
+		return set_slice(v, list, env);
+	}
 
-	let value = eval_expression(&v.value, env)?;
+	// the list can't stay borrowed while evaluating the key and the value, as
+	// they may use the list as well
+	let n = eval_subscription(&v.key, &v.blame, env)?;
+
+	let index = normalize_index(n, &v.blame, list.borrow().len())?;
+
+	let value = if let Some(operator) = &v.operator {
+		// evaluating the key could have shrunk the list
+		let current = list.borrow().get(index).cloned().ok_or_else(|| {
+			RuntimeError::new(
+				format!("Index {} out of bounds", index),
+				v.blame.clone(),
+			)
+		})?;
+
+		binary_operation(operator, &current, &eval_expression(&v.value, env)?)?
+	} else {
+		eval_expression(&v.value, env)?
+	};
+
+	// the value could have shrunk the list in the meantime
+	if let Some(element) = list.borrow_mut().get_mut(index) {
+		*element = value.clone();
+	} else {
+		return Err(RuntimeError::new(
+			format!("Index {} out of bounds", index),
+			v.blame.clone(),
+		));
+	}
+
+	Ok(value)
+}
+
+/// Replaces the elements selected by a slice with elements of a list,
+/// contiguous slices can be replaced with any number of elements
+fn set_slice(
+	v: &SetValue,
+	list: &Rc<RefCell<Vec<InterpreterValue>>>,
+	env: &InterpreterEnvironment,
+) -> Result<InterpreterValue, RuntimeError> {
+	let len = list.borrow().len();
+
+	let slice = extract_slice(&v.key, &v.blame, len, env)?;
 
-	l_borrow[index] = value.clone();
+	let value = if let Some(operator) = &v.operator {
+		let current = {
+			let l_borrow = list.borrow();
+
+			InterpreterValue::List(Rc::new(RefCell::new(
+				slice
+					.indices
+					.iter()
+					.filter_map(|i| l_borrow.get(*i).cloned())
+					.collect(),
+			)))
+		};
+
+		binary_operation(operator, &current, &eval_expression(&v.value, env)?)?
+	} else {
+		eval_expression(&v.value, env)?
+	};
+
+	let values = if let InterpreterValue::List(values) = &value {
+		values.borrow().clone()
+	} else {
+		return Err(RuntimeError::new(
+			format!(
+				"Cannot assign {} to a slice, expected a list",
+				value.human_type()
+			),
+			v.blame.clone(),
+		));
+	};
+
+	let mut l_borrow = list.borrow_mut();
+
+	// the value could have shrunk the list in the meantime
+	if slice.start > l_borrow.len()
+		|| slice.indices.iter().any(|i| *i >= l_borrow.len())
+	{
+		return Err(RuntimeError::new(
+			"Slice out of bounds".into(),
+			v.blame.clone(),
+		));
+	}
+
+	if slice.step == 1 {
+		let end = slice.start + slice.indices.len();
+
+		l_borrow.splice(slice.start..end, values);
+	} else if values.len() == slice.indices.len() {
+		for (i, value) in slice.indices.iter().zip(values) {
+			l_borrow[*i] = value;
+		}
+	} else {
+		return Err(RuntimeError::new(
+			format!(
+				"Cannot assign {} elements to a slice of {} elements",
+				values.len(),
+				slice.indices.len()
+			),
+			v.blame.clone(),
+		));
+	}
 
 	Ok(value)
 }
@@ -525,11 +1299,9 @@ pub fn super_expression(
 		.read(
 			env_distance - 1,
 			&Token {
-				location: Location {
-					byte_offset: 0,
-					byte_length: 0,
-				},
+				location: v.blame.location,
 				token_type: TokenType::This,
+				leading_trivia: Vec::new(),
 			},
 		)?
 		.value;
@@ -551,9 +1323,11 @@ pub fn super_expression(
 					)
 				};
 
-			let constructor = constructor.ok_or_else(|| RuntimeError {
-				message: "Superclass does not have a constructor".into(),
-				token: v.blame.clone(),
+			let constructor = constructor.ok_or_else(|| {
+				RuntimeError::new(
+					"Superclass does not have a constructor".into(),
+					v.blame.clone(),
+				)
 			})?;
 
 			let constructor = bind_function(&constructor, instance);
@@ -582,3 +1356,313 @@ pub fn object_expression(
 		properties: Rc::new(RefCell::new(properties)),
 	})
 }
+
+pub fn template_expression(
+	v: &TemplateValue,
+	env: &InterpreterEnvironment,
+) -> Result<InterpreterValue, RuntimeError> {
+	let mut result = String::new();
+
+	// stringifying the same way `str` does
+	for part in &v.parts {
+		result += &eval_expression(part, env)?.to_string();
+	}
+
+	Ok(InterpreterValue::String(Rc::from(result)))
+}
+
+pub fn conditional_expression(
+	v: &ConditionalValue,
+	env: &InterpreterEnvironment,
+) -> Result<InterpreterValue, RuntimeError> {
+	if eval_expression(&v.condition, env)? == InterpreterValue::True {
+		eval_expression(&v.then, env)
+	} else if let Some(otherwise) = &v.otherwise {
+		eval_expression(otherwise, env)
+	} else {
+		Ok(InterpreterValue::Nil)
+	}
+}
+
+pub fn sequence_expression(
+	v: &SequenceValue,
+	env: &InterpreterEnvironment,
+) -> Result<InterpreterValue, RuntimeError> {
+	let new_scope = env.fork();
+
+	guard_sequence(eval_statements(&v.statements, &new_scope)?)?;
+
+	v.value.as_ref().map_or(Ok(InterpreterValue::Nil), |value| {
+		eval_expression(value, &new_scope)
+	})
+}
+
+pub fn match_expression(
+	v: &MatchValue,
+	env: &InterpreterEnvironment,
+) -> Result<InterpreterValue, RuntimeError> {
+	let value = eval_expression(&v.value, env)?;
+
+	for arm in &v.arms {
+		// bindings live in a scope of their own arm
+		let arm_env = env.fork();
+
+		if !match_pattern(&arm.pattern, &value, env, &arm_env)? {
+			continue;
+		}
+
+		if let Some(guard) = &arm.guard {
+			if eval_expression(guard, &arm_env)? != InterpreterValue::True {
+				continue;
+			}
+		}
+
+		return eval_expression(&arm.body, &arm_env);
+	}
+
+	Err(RuntimeError::new(
+		format!("No arm matched {}", value.repr(true)),
+		v.keyword.clone(),
+	))
+}
+
+/// Checks if the value matches the pattern, declaring pattern's bindings in
+/// `arm_env` along the way
+#[allow(clippy::too_many_lines)]
+fn match_pattern(
+	pattern: &Pattern,
+	value: &InterpreterValue,
+	env: &InterpreterEnvironment,
+	arm_env: &InterpreterEnvironment,
+) -> Result<bool, RuntimeError> {
+	let bind = |binding: &Option<Token>, value: InterpreterValue| {
+		if let Some(binding) = binding {
+			arm_env.declare(
+				assume_identifier(binding).to_owned(),
+				DeclaredValue {
+					mutable: false,
+					value,
+				},
+			);
+		}
+	};
+
+	match pattern {
+		Pattern::Literal(expr) => Ok(eval_expression(expr, env)? == *value),
+		Pattern::Binding(binding) => {
+			bind(binding, value.clone());
+
+			Ok(true)
+		}
+		Pattern::Type { kind, binding } => {
+			let matched = match kind {
+				PatternType::Builtin(name) => match &**name {
+					"object" => {
+						matches!(value, InterpreterValue::Instance { .. })
+					}
+					"boolean" => matches!(
+						value,
+						InterpreterValue::True | InterpreterValue::False
+					),
+					name => value.human_type() == name,
+				},
+				PatternType::Class(class) => {
+					is_instance_of(value, &pattern_class(class, env)?)
+				}
+			};
+
+			if matched {
+				bind(binding, value.clone());
+			}
+
+			Ok(matched)
+		}
+		Pattern::List {
+			before,
+			rest,
+			after,
+		} => {
+			let elements = if let InterpreterValue::List(l) = value {
+				l.borrow().clone()
+			} else {
+				return Ok(false);
+			};
+
+			let fixed_len = before.len() + after.len();
+
+			if elements.len() < fixed_len
+				|| (rest.is_none() && elements.len() != fixed_len)
+			{
+				return Ok(false);
+			}
+
+			let after_start = elements.len() - after.len();
+
+			let fixed_patterns = before
+				.iter()
+				.zip(&elements)
+				.chain(after.iter().zip(&elements[after_start..]));
+
+			for (element_pattern, element) in fixed_patterns {
+				if !match_pattern(element_pattern, element, env, arm_env)? {
+					return Ok(false);
+				}
+			}
+
+			if let Some(rest) = rest {
+				let rest_value = InterpreterValue::List(Rc::new(RefCell::new(
+					elements[before.len()..after_start].to_vec(),
+				)));
+
+				return match_pattern(rest, &rest_value, env, arm_env);
+			}
+
+			Ok(true)
+		}
+		Pattern::Object { class, properties } => {
+			if !matches!(
+				value,
+				InterpreterValue::Instance { .. }
+					| InterpreterValue::Module(_)
+					| InterpreterValue::Variant { .. }
+			) {
+				return Ok(false);
+			}
+
+			if let Some(class) = class {
+				if !is_instance_of(value, &pattern_class(class, env)?) {
+					return Ok(false);
+				}
+			}
+
+			for property in properties {
+				match pattern_property(value, &property.key) {
+					Some(property_value)
+						if match_pattern(
+							&property.pattern,
+							&property_value,
+							env,
+							arm_env,
+						)? => {}
+					_ => return Ok(false),
+				}
+			}
+
+			Ok(true)
+		}
+	}
+}
+
+/// Evaluates the class a pattern refers to
+pub fn pattern_class(
+	class: &Expr,
+	env: &InterpreterEnvironment,
+) -> Result<InterpreterValue, RuntimeError> {
+	let evaluated = eval_expression(class, env)?;
+
+	let is_pattern_class = match &evaluated {
+		InterpreterValue::Class { .. }
+		| InterpreterValue::Enum(_)
+		| InterpreterValue::Variant { .. } => true,
+		InterpreterValue::Function { fun, .. } => {
+			matches!(**fun, InterpreterFunction::VariantConstructor { .. })
+		}
+		_ => false,
+	};
+
+	if is_pattern_class {
+		return Ok(evaluated);
+	}
+
+	let token = match class {
+		Expr::Identifier(iv) => iv.name.clone(),
+		Expr::Get(gv) => gv.blame.clone(),
+		_ => unreachable!("Pattern class should be an identifier or a path"),
+	};
+
+	Err(RuntimeError::new(
+		format!(
+			"Expected a class or an enum in pattern, found {}",
+			evaluated.human_type()
+		),
+		token,
+	))
+}
+
+/// Checks if the value is an instance of the class or of its subclass, or a
+/// value of the enum or of the variant
+pub fn is_instance_of(
+	value: &InterpreterValue,
+	class: &InterpreterValue,
+) -> bool {
+	match (value, class) {
+		(
+			InterpreterValue::Instance {
+				class: Some(instance_class),
+				..
+			},
+			_,
+		) => inherits_from(instance_class, class),
+		(
+			InterpreterValue::Variant { enumeration, .. },
+			InterpreterValue::Enum(class_enumeration),
+		) => Rc::ptr_eq(enumeration, class_enumeration),
+		(
+			InterpreterValue::Variant {
+				enumeration, index, ..
+			},
+			InterpreterValue::Function { fun, .. },
+		) => matches!(
+			&**fun,
+			InterpreterFunction::VariantConstructor {
+				enumeration: class_enumeration,
+				index: class_index,
+			} if Rc::ptr_eq(enumeration, class_enumeration)
+				&& index == class_index
+		),
+		(
+			InterpreterValue::Variant { .. },
+			InterpreterValue::Variant { .. },
+		) => value == class,
+		_ => false,
+	}
+}
+
+/// Reads a property checked by an object pattern, which is either a property
+/// of an instance or a field of a variant
+pub fn pattern_property(
+	value: &InterpreterValue,
+	key: &str,
+) -> Option<InterpreterValue> {
+	match value {
+		InterpreterValue::Instance { properties, .. } => {
+			properties.borrow().get(key).cloned()
+		}
+		InterpreterValue::Module(exports) => exports.get(key).cloned(),
+		InterpreterValue::Variant { .. } => variant_field(value, key),
+		_ => None,
+	}
+}
+
+fn inherits_from(checked: &InterpreterValue, class: &InterpreterValue) -> bool {
+	match (checked, class) {
+		(
+			InterpreterValue::Class {
+				methods,
+				superclass,
+				..
+			},
+			InterpreterValue::Class {
+				methods: class_methods,
+				..
+			},
+		) => {
+			// every class has its own methods map, so it identifies it
+			Rc::ptr_eq(methods, class_methods)
+				|| superclass
+					.as_ref()
+					.is_some_and(|superclass| inherits_from(superclass, class))
+		}
+		_ => false,
+	}
+}
@@ -4,12 +4,16 @@ use super::{
 	types::{InterpreterFunction, InterpreterValue, RuntimeError, StmtResult},
 };
 use crate::{
-	ast::expr::{FunctionValue, GetAccessor},
+	ast::expr::{Expr, FunctionValue, GetAccessor, Parameter},
 	env::{DeclaredValue, EnvironmentWrapper},
 	token::{Token, TokenType},
 };
 
-use std::{cell::RefMut, rc::Rc};
+use std::{
+	cell::{RefCell, RefMut},
+	convert::TryFrom,
+	rc::Rc,
+};
 
 
 #[macro_export]
@@ -42,59 +46,115 @@ pub fn guard_function(
 	ibv: StmtResult<InterpreterValue>,
 ) -> Result<InterpreterValue, RuntimeError> {
 	match ibv {
-		StmtResult::Break(token) => Err(RuntimeError {
-			message: "Cannot use `break` outside of a loop".into(),
+		StmtResult::Break { keyword: token, .. } => Err(RuntimeError::new(
+			"Cannot use `break` outside of a loop".into(),
 			token,
-		}),
-		StmtResult::Continue(token) => Err(RuntimeError {
-			message: "Cannot use `continue` outside of a loop".into(),
+		)),
+		StmtResult::Continue { keyword: token, .. } => Err(RuntimeError::new(
+			"Cannot use `continue` outside of a loop".into(),
 			token,
-		}),
+		)),
 		StmtResult::Return { value, .. } => Ok(value),
 		StmtResult::Noop => Ok(InterpreterValue::Nil),
 	}
 }
 
+/// Same as `guard_function`, but for blocks of `if` expressions, which can't
+/// be left with `return` either
+pub fn guard_sequence(
+	ibv: StmtResult<InterpreterValue>,
+) -> Result<(), RuntimeError> {
+	let (keyword, token) = match ibv {
+		StmtResult::Break { keyword: token, .. } => ("break", token),
+		StmtResult::Continue { keyword: token, .. } => ("continue", token),
+		StmtResult::Return { keyword, .. } => ("return", keyword),
+		StmtResult::Noop => return Ok(()),
+	};
+
+	Err(RuntimeError::new(
+		format!("Cannot use `{}` inside of an `if` expression", keyword),
+		token,
+	))
+}
+
 #[inline]
 pub fn confirm_arity(
-	target: usize,
+	min: usize,
+	max: Option<usize>,
 	value: usize,
 	blame: &Token,
 ) -> Result<(), RuntimeError> {
-	if target == value {
-		Ok(())
-	} else {
-		Err(RuntimeError {
-			message: format!(
-				"{} arguments",
-				if value > target {
-					"Too many"
-				} else {
-					"Not enough"
-				}
-			),
-			token: blame.clone(),
-		})
+	if value >= min && max.is_none_or(|max| value <= max) {
+		return Ok(());
 	}
+
+	let expected = match max {
+		Some(max) if max == min => format!("{}", min),
+		Some(max) => format!("{} to {}", min, max),
+		None => format!("at least {}", min),
+	};
+
+	Err(RuntimeError::new(
+		format!(
+			"{} arguments, expected {}, got {}",
+			if max.is_some_and(|max| value > max) {
+				"Too many"
+			} else {
+				"Not enough"
+			},
+			expected,
+			value
+		),
+		blame.clone(),
+	))
 }
 
-#[inline]
+/// Minimal and maximal (if there's no rest parameter) argument count
+pub fn function_arity(parameters: &[Parameter]) -> (usize, Option<usize>) {
+	let min = parameters
+		.iter()
+		.filter(|param| param.default.is_none() && !param.rest)
+		.count();
+
+	let max = if parameters.iter().any(|param| param.rest) {
+		None
+	} else {
+		Some(parameters.len())
+	};
+
+	(min, max)
+}
+
+/// Declares the parameters in `fun_env`, evaluating default values of the
+/// missing ones and gathering the remaining arguments into the rest parameter
 pub fn map_arguments(
-	parameters: &[Token],
+	parameters: &[Parameter],
 	arguments: &[InterpreterValue],
 	fun_env: &InterpreterEnvironment,
-) {
-	parameters.iter().zip(arguments).for_each(|(param, arg)| {
-		let name = assume_identifier(param);
+) -> Result<(), RuntimeError> {
+	for (i, param) in parameters.iter().enumerate() {
+		let value = if param.rest {
+			InterpreterValue::List(Rc::new(RefCell::new(
+				arguments.get(i..).unwrap_or_default().to_vec(),
+			)))
+		} else if let Some(argument) = arguments.get(i) {
+			argument.clone()
+		} else if let Some(default) = &param.default {
+			eval_expression(default, fun_env)?
+		} else {
+			unreachable!("Arity should be confirmed before mapping arguments")
+		};
 
 		fun_env.declare(
-			name.to_string(),
+			assume_identifier(&param.name).to_owned(),
 			DeclaredValue {
 				mutable: true,
-				value: arg.clone(),
+				value,
 			},
 		);
-	});
+	}
+
+	Ok(())
 }
 
 #[inline]
@@ -109,6 +169,7 @@ pub fn construct_lox_defined_function(
 			keyword: fv.keyword.clone(),
 			name: fv.name.clone(),
 			params: fv.params.as_ref().map(Rc::clone),
+			doc: fv.doc.clone(),
 		})),
 	}
 }
@@ -148,13 +209,129 @@ pub fn unwrap_list<'a>(
 	if let InterpreterValue::List(l) = &value {
 		Ok(l.borrow_mut())
 	} else {
-		Err(RuntimeError {
-			message: override_msg.unwrap_or_else(|| {
+		Err(RuntimeError::new(
+			override_msg.unwrap_or_else(|| {
 				format!("Argument {} must be of type list", arg_index)
 			}),
-			token: blame.clone(),
+			blame.clone(),
+		))
+	}
+}
+
+#[inline]
+pub fn unwrap_string<'a>(
+	value: &'a InterpreterValue,
+	blame: &Token,
+	arg_index: usize,
+) -> Result<&'a Rc<str>, RuntimeError> {
+	if let InterpreterValue::String(s) = &value {
+		Ok(s)
+	} else {
+		Err(RuntimeError::new(
+			format!("Argument {} must be of type string", arg_index),
+			blame.clone(),
+		))
+	}
+}
+
+/// Converts an index (e.g. of a list's element) to a number value
+pub fn index_to_number(
+	index: usize,
+	blame: &Token,
+) -> Result<InterpreterValue, RuntimeError> {
+	try_exact_convert!(index, usize, f64)
+		.map_err(|_| {
+			RuntimeError::new(
+				format!("Cannot conver from {}_usize to f64", index),
+				blame.clone(),
+			)
 		})
+		.map(InterpreterValue::Number)
+}
+
+/// Converts a number to an integer for bitwise operations, erroring if it has
+/// a fractional part or doesn't fit
+pub fn number_to_integer(n: f64, blame: &Token) -> Result<i64, RuntimeError> {
+	// 2^63, exactly representable as a float, but one past `i64::MAX`
+	const INTEGER_BOUND: f64 = 9_223_372_036_854_775_808.0;
+
+	// infinities have a NaN fractional part, but belong to the error below
+	#[allow(clippy::float_cmp)]
+	if n.fract() != 0.0 && !n.is_infinite() {
+		return Err(RuntimeError::new(
+			format!("Cannot use `{}` on non-integer {}", blame.token_type, n),
+			blame.clone(),
+		));
 	}
+
+	if !(-INTEGER_BOUND..INTEGER_BOUND).contains(&n) {
+		return Err(RuntimeError::new(
+			format!(
+				"Cannot use `{}` on {}, it's out of the 64-bit integer range",
+				blame.token_type, n
+			),
+			blame.clone(),
+		));
+	}
+
+	// the checks above make the conversion exact
+	#[allow(clippy::as_conversions, clippy::cast_possible_truncation)]
+	Ok(n as i64)
+}
+
+/// Converts the result of a bitwise operation back to a number
+pub fn integer_to_number(
+	i: i64,
+	blame: &Token,
+) -> Result<InterpreterValue, RuntimeError> {
+	try_exact_convert!(i, i64, f64)
+		.map_err(|_| {
+			RuntimeError::new(
+				format!(
+					"Result of `{}` ({}) cannot be represented as a number",
+					blame.token_type, i
+				),
+				blame.clone(),
+			)
+		})
+		.map(InterpreterValue::Number)
+}
+
+/// Creates a range out of the operands of `..` or `..=`
+pub fn make_range(
+	operator: &Token,
+	n1: f64,
+	n2: f64,
+) -> Result<InterpreterValue, RuntimeError> {
+	let start = number_to_integer(n1, operator)?;
+	let end = number_to_integer(n2, operator)?;
+
+	let end = if operator.token_type == TokenType::DotDotEqual {
+		end.checked_add(1).ok_or_else(|| {
+			RuntimeError::new(
+				format!("Range end {} is too big", n2),
+				operator.clone(),
+			)
+		})?
+	} else {
+		end
+	};
+
+	Ok(InterpreterValue::Range { start, end })
+}
+
+/// Number of elements in a range, ranges with `end` before `start` are empty
+pub fn range_len(start: i64, end: i64) -> usize {
+	usize::try_from(end.saturating_sub(start)).unwrap_or(0)
+}
+
+/// Lazily yields the numbers of a range
+#[allow(clippy::as_conversions, clippy::cast_precision_loss)]
+pub fn range_values(
+	start: i64,
+	end: i64,
+) -> impl Iterator<Item = InterpreterValue> {
+	(start..end).map(|i| InterpreterValue::Number(i as f64))
 }
 
 pub fn extract_subscription_index(
@@ -163,7 +340,19 @@ pub fn extract_subscription_index(
 	max_len: usize,
 	env: &InterpreterEnvironment,
 ) -> Result<usize, RuntimeError> {
-	let extracted_n = match &accessor {
+	let extracted_n = eval_subscription(accessor, blame, env)?;
+
+	normalize_index(extracted_n, blame, max_len)
+}
+
+/// Evaluates the key of a subscription, without checking it against the
+/// length, for values that the key can modify (e.g. lists)
+pub fn eval_subscription(
+	accessor: &GetAccessor,
+	blame: &Token,
+	env: &InterpreterEnvironment,
+) -> Result<f64, RuntimeError> {
+	match &accessor {
 		GetAccessor::SubscriptionNumber(n) => Ok(*n),
 		GetAccessor::SubscriptionEval(expr) => {
 			let eval = eval_expression(expr, env)?;
@@ -171,34 +360,148 @@ pub fn extract_subscription_index(
 			if let InterpreterValue::Number(n) = eval {
 				Ok(n)
 			} else {
-				Err(RuntimeError {
-					message: format!(
-						"Cannot use {} for indexing",
-						eval.human_type()
-					),
-					token: blame.clone(),
-				})
+				Err(RuntimeError::new(
+					format!("Cannot use {} for indexing", eval.human_type()),
+					blame.clone(),
+				))
 			}
 		}
 		_ => unreachable!("Wrong accessor in subscription"),
-	}?;
+	}
+}
 
-	let index = try_exact_convert!(extracted_n, f64, usize).map_err(|_| {
-		RuntimeError {
-			message: format!(
-				"Cannot access element on erroneous index {}",
-				extracted_n
-			),
-			token: blame.clone(),
-		}
+/// Checks an index against the length of the indexed value, negative indices
+/// count from the end
+pub fn normalize_index(
+	n: f64,
+	blame: &Token,
+	max_len: usize,
+) -> Result<usize, RuntimeError> {
+	let index = try_exact_convert!(n, f64, i64).map_err(|_| {
+		RuntimeError::new(
+			format!("Cannot access element on erroneous index {}", n),
+			blame.clone(),
+		)
 	})?;
 
-	if index >= max_len {
-		Err(RuntimeError {
-			message: format!("Index {} out of bounds", extracted_n),
-			token: blame.clone(),
-		})
+	let index = if index < 0 {
+		index.checked_add(len_to_integer(max_len))
 	} else {
-		Ok(index)
+		Some(index)
+	};
+
+	match index.and_then(|index| usize::try_from(index).ok()) {
+		Some(index) if index < max_len => Ok(index),
+		_ => Err(RuntimeError::new(
+			format!("Index {} out of bounds", n),
+			blame.clone(),
+		)),
 	}
 }
+
+/// Elements selected by a slice, bounds being normalized like in Python
+pub struct Slice {
+	/// where the slice starts, even when it's empty
+	pub start: usize,
+	pub step: i64,
+	pub indices: Vec<usize>,
+}
+
+/// Evaluates the bounds of a slice against a value of the given length
+pub fn extract_slice(
+	accessor: &GetAccessor,
+	blame: &Token,
+	max_len: usize,
+	env: &InterpreterEnvironment,
+) -> Result<Slice, RuntimeError> {
+	let (start, end, step) =
+		if let GetAccessor::Slice { start, end, step } = accessor {
+			(start, end, step)
+		} else {
+			unreachable!("Wrong accessor in slice")
+		};
+
+	let len = len_to_integer(max_len);
+
+	let bound = |bound: &Option<Box<Expr>>| -> Result<_, RuntimeError> {
+		bound
+			.as_ref()
+			.map(|expr| slice_bound(expr, blame, env))
+			.transpose()
+	};
+
+	// negative bounds count from the end
+	let normalize = |i: i64| if i < 0 { i.saturating_add(len) } else { i };
+
+	let step = bound(step)?.unwrap_or(1);
+
+	if step == 0 {
+		return Err(RuntimeError::new(
+			"Slice step cannot be zero".into(),
+			blame.clone(),
+		));
+	}
+
+	// backward slices go from the last element down to before the first one
+	let (start, end) = if step > 0 {
+		(
+			bound(start)?.map_or(0, normalize).clamp(0, len),
+			bound(end)?.map_or(len, normalize).clamp(0, len),
+		)
+	} else {
+		(
+			bound(start)?.map_or(len - 1, normalize).clamp(-1, len - 1),
+			bound(end)?.map_or(-1, normalize).clamp(-1, len - 1),
+		)
+	};
+
+	let mut indices = Vec::new();
+	let mut i = start;
+
+	while (step > 0 && i < end) || (step < 0 && i > end) {
+		// `i` is within `0..len` here, so it fits in usize
+		indices.push(usize::try_from(i).unwrap_or_else(|_| {
+			unreachable!("Slice index is out of bounds \u{1f914}")
+		}));
+
+		i = match i.checked_add(step) {
+			Some(i) => i,
+			None => break,
+		};
+	}
+
+	Ok(Slice {
+		start: usize::try_from(start.max(0)).unwrap_or(0),
+		step,
+		indices,
+	})
+}
+
+fn slice_bound(
+	expr: &Expr,
+	blame: &Token,
+	env: &InterpreterEnvironment,
+) -> Result<i64, RuntimeError> {
+	let eval = eval_expression(expr, env)?;
+
+	let n = if let InterpreterValue::Number(n) = eval {
+		n
+	} else {
+		return Err(RuntimeError::new(
+			format!("Cannot use {} for slicing", eval.human_type()),
+			blame.clone(),
+		));
+	};
+
+	try_exact_convert!(n, f64, i64).map_err(|_| {
+		RuntimeError::new(
+			format!("Cannot slice with erroneous bound {}", n),
+			blame.clone(),
+		)
+	})
+}
+
+/// Lengths of values don't get anywhere close to `i64::MAX`
+fn len_to_integer(len: usize) -> i64 {
+	i64::try_from(len).unwrap_or(i64::MAX)
+}
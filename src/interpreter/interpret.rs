@@ -4,16 +4,23 @@ use super::{
 		assignment_expression,
 		binary_experssion,
 		call_expression,
+		conditional_expression,
+		destructuring_assignment_expression,
 		function_expression,
 		get_expression,
 		identifier_expression,
 		literal_expression,
+		match_expression,
 		object_expression,
+		optional_chain_expression,
+		sequence_expression,
 		set_expression,
 		super_expression,
+		template_expression,
 		this_expression,
 		unary_expression,
 	},
+	modules::import_statement,
 	native_functions,
 	statements::{
 		block_statement,
@@ -21,42 +28,85 @@ use super::{
 		class_statement,
 		continue_statement,
 		declaration_statement,
+		destructure_statement,
+		enum_statement,
 		expression_statement,
+		for_in_statement,
 		for_statement,
 		if_statement,
 		print_statement,
 		return_statement,
+		throw_statement,
+		try_statement,
 	},
 	types::{InterpreterValue, RuntimeError, StmtResult},
 };
 use crate::{
-	ast::{expr::Expr, stmt::Stmt},
-	env::EnvironmentWrapper,
+	ast::{
+		expr::{Expr, LiteralValue},
+		stmt::{ExpressionValue, Stmt},
+	},
+	source::{FileId, SourceMap},
 };
 
+use std::{cell::RefCell, fs, rc::Rc};
+
+
+pub fn interpret(
+	statements: &[Stmt],
+	files: &Rc<RefCell<SourceMap>>,
+	file: FileId,
+) -> Result<(), RuntimeError> {
+	let env = InterpreterEnvironment::with_files(files.clone());
 
-pub fn interpret(statements: &[Stmt]) -> Result<(), RuntimeError> {
-	let env = InterpreterEnvironment::new();
+	// so that modules importing the main file are reported as a cycle
+	if let Ok(path) = fs::canonicalize(&*files.borrow().get(file).name) {
+		env.program().importing.borrow_mut().push(path);
+	}
 
-	native_functions::declare(&env);
+	eval_program(statements, file, &env)
+}
 
-	match eval_statements(statements, &env)? {
+/// Evaluates top-level statements of a program or a module
+pub fn eval_program(
+	statements: &[Stmt],
+	file: FileId,
+	env: &InterpreterEnvironment,
+) -> Result<(), RuntimeError> {
+	if uses_chars(statements) {
+		env.program().char_files.borrow_mut().push(file);
+	}
+
+	native_functions::declare(env);
+
+	match eval_statements(statements, env)? {
 		StmtResult::Noop => Ok(()),
-		StmtResult::Break(token) => Err(RuntimeError {
-			message: "Cannot use `break` outside of a loop".into(),
+		StmtResult::Break { keyword: token, .. } => Err(RuntimeError::new(
+			"Cannot use `break` outside of a loop".into(),
 			token,
-		}),
-		StmtResult::Continue(token) => Err(RuntimeError {
-			message: "Cannot use `continue` outside of a loop".into(),
+		)),
+		StmtResult::Continue { keyword: token, .. } => Err(RuntimeError::new(
+			"Cannot use `continue` outside of a loop".into(),
 			token,
-		}),
-		StmtResult::Return { keyword, .. } => Err(RuntimeError {
-			message: "Cannot use `return` outside of a function".into(),
-			token: keyword,
-		}),
+		)),
+		StmtResult::Return { keyword, .. } => Err(RuntimeError::new(
+			"Cannot use `return` outside of a function".into(),
+			keyword,
+		)),
 	}
 }
 
+/// `"use chars";` as the first statement of a file makes `[]` and `len` work
+/// on chars of strings instead of bytes
+fn uses_chars(statements: &[Stmt]) -> bool {
+	matches!(
+		statements.first(),
+		Some(Stmt::Expression(ExpressionValue {
+			expression: Expr::Literal(LiteralValue::String(pragma)),
+		})) if &**pragma == "use chars"
+	)
+}
+
 pub fn eval_statements(
 	statements: &[Stmt],
 	env: &InterpreterEnvironment,
@@ -72,7 +122,7 @@ pub fn eval_statements(
 	Ok(StmtResult::Noop)
 }
 
-fn eval_statement(
+pub fn eval_statement(
 	stmt: &Stmt,
 	env: &InterpreterEnvironment,
 ) -> Result<StmtResult<InterpreterValue>, RuntimeError> {
@@ -80,13 +130,20 @@ fn eval_statement(
 		Stmt::Expression(v) => expression_statement(eval_expression, v, env),
 		Stmt::Print(v) => print_statement(eval_expression, v, env),
 		Stmt::Declaration(v) => declaration_statement(eval_expression, v, env),
+		Stmt::Destructure(v) => destructure_statement(v, env),
 		Stmt::Block(v) => block_statement(eval_statements, v, env),
 		Stmt::If(v) => if_statement(eval_expression, eval_statement, v, env),
 		Stmt::For(v) => for_statement(eval_expression, eval_statement, v, env),
+		Stmt::ForIn(v) => for_in_statement(v, env),
 		Stmt::Return(v) => return_statement(eval_expression, v, env),
 		Stmt::Break(v) => Ok(break_statement(v)),
 		Stmt::Continue(v) => Ok(continue_statement(v)),
 		Stmt::Class(v) => class_statement(v, env),
+		Stmt::Enum(v) => Ok(enum_statement(v, env)),
+		Stmt::Throw(v) => throw_statement(v, env),
+		Stmt::Try(v) => try_statement(v, env),
+		Stmt::Import(v) => import_statement(v, env),
+		Stmt::Export(v) => eval_statement(&v.declaration, env),
 	}
 }
 
@@ -100,7 +157,10 @@ pub fn eval_expression(
 		Expr::Unary(v) => unary_expression(v, env),
 		Expr::Binary(v) => binary_experssion(v, env),
 		Expr::Identifier(v) => identifier_expression(v, env),
-		Expr::Assignment(v) => assignment_expression(eval_expression, v, env),
+		Expr::Assignment(v) => assignment_expression(v, env),
+		Expr::DestructuringAssignment(v) => {
+			destructuring_assignment_expression(v, env)
+		}
 		Expr::Call(v) => call_expression(v, env),
 		Expr::Function(v) => Ok(function_expression(v, env)),
 		Expr::Get(v) => get_expression(v, env),
@@ -108,5 +168,13 @@ pub fn eval_expression(
 		Expr::This(v) => this_expression(v, env),
 		Expr::Super(v) => super_expression(v, env),
 		Expr::Object(v) => object_expression(v, env),
+		Expr::Template(v) => template_expression(v, env),
+		Expr::Conditional(v) => conditional_expression(v, env),
+		Expr::Sequence(v) => sequence_expression(v, env),
+		Expr::Match(v) => match_expression(v, env),
+		Expr::OptionalChain(v) => optional_chain_expression(v, env),
+		Expr::Spread(_) => {
+			unreachable!("Spreads are only parsed in lists and call arguments")
+		}
 	}
 }
@@ -1,4 +1,5 @@
 mod interpret;
+mod modules;
 mod pn;
 
 pub mod env;
@@ -0,0 +1,161 @@
+use super::{
+	env::InterpreterEnvironment,
+	helpers::assume_identifier,
+	interpret::eval_program,
+	types::{InterpreterValue, RuntimeError, StmtResult},
+};
+use crate::{
+	ast::stmt::{ImportValue, Stmt},
+	env::{DeclaredValue, EnvironmentWrapper},
+	parser,
+	resolver,
+	runner::DescribableError,
+	scanner,
+	token::{Token, TokenType},
+};
+
+use std::{
+	collections::HashMap,
+	fs,
+	iter,
+	path::{Path, PathBuf},
+	rc::Rc,
+};
+
+
+pub fn import_statement(
+	v: &ImportValue,
+	env: &InterpreterEnvironment,
+) -> Result<StmtResult<InterpreterValue>, RuntimeError> {
+	let module = import_module(v, env)?;
+
+	env.declare(
+		assume_identifier(&v.name).to_owned(),
+		DeclaredValue {
+			mutable: false,
+			value: module,
+		},
+	);
+
+	Ok(StmtResult::Noop)
+}
+
+/// Evaluates the module (only the first time it's imported) and returns an
+/// object with its exports
+fn import_module(
+	v: &ImportValue,
+	env: &InterpreterEnvironment,
+) -> Result<InterpreterValue, RuntimeError> {
+	let import_error =
+		|message: String| RuntimeError::new(message, v.blame.clone());
+
+	// paths are relative to the importing file
+	let name = {
+		let files = env.files().borrow();
+		let importer = &files.get(v.blame.location.file).name;
+
+		Path::new(&**importer)
+			.parent()
+			.unwrap_or_else(|| Path::new(""))
+			.join(&*v.path)
+	};
+
+	let path = fs::canonicalize(&name).map_err(|err| {
+		import_error(format!("Cannot import `{}`: {}", v.path, err))
+	})?;
+
+	if let Some(module) = env.program().modules.borrow().get(&path) {
+		return Ok(module.clone());
+	}
+
+	if let Some(cycle) = import_cycle(&path, env) {
+		return Err(import_error(format!("Import cycle: {}", cycle)));
+	}
+
+	let source = fs::read_to_string(&path).map_err(|err| {
+		import_error(format!("Cannot import `{}`: {}", v.path, err))
+	})?;
+
+	let file = env
+		.files()
+		.borrow_mut()
+		.add(&name.to_string_lossy(), &source);
+
+	let (tokens, errors) = scanner::scan(&source, file);
+
+	if let Some(error) = errors.first() {
+		return Err(module_error("Scan", error));
+	}
+
+	let (statements, errors) = parser::parse(tokens);
+
+	if let Some(error) = errors.first() {
+		return Err(module_error("Parse", error));
+	}
+
+	resolver::resolve(&statements)?;
+
+	let module_env = env.module();
+
+	env.program().importing.borrow_mut().push(path.clone());
+	let evaluated = eval_program(&statements, file, &module_env);
+	env.program().importing.borrow_mut().pop();
+
+	evaluated?;
+
+	let mut exports = HashMap::new();
+
+	for stmt in &statements {
+		if let Stmt::Export(export) = stmt {
+			for name in &export.names {
+				exports.insert(
+					assume_identifier(name).to_owned(),
+					module_env.read(0, name)?.value,
+				);
+			}
+		}
+	}
+
+	let module = InterpreterValue::Module(Rc::new(exports));
+
+	env.program()
+		.modules
+		.borrow_mut()
+		.insert(path, module.clone());
+
+	Ok(module)
+}
+
+/// Describes the chain of imports leading back to `path`, if it's still
+/// being evaluated
+fn import_cycle(path: &Path, env: &InterpreterEnvironment) -> Option<String> {
+	let importing = env.program().importing.borrow();
+
+	let start = importing.iter().position(|p| p == path)?;
+
+	Some(
+		importing[start..]
+			.iter()
+			.map(PathBuf::as_path)
+			.chain(iter::once(path))
+			.map(|p| p.display().to_string())
+			.collect::<Vec<String>>()
+			.join(" -> "),
+	)
+}
+
+/// Turns an error from scanning or parsing a module into a runtime error,
+/// pointing at the module's source
+fn module_error<E>(category: &str, error: &E) -> RuntimeError
+where
+	E: DescribableError,
+{
+	RuntimeError::new(
+		format!("{} error: {}", category, error.description()),
+		Token {
+			location: error.location(),
+			token_type: TokenType::Import,
+			leading_trivia: Vec::new(),
+		},
+	)
+}
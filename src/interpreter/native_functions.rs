@@ -1,6 +1,13 @@
 use super::{
 	env::InterpreterEnvironment,
-	helpers::unwrap_list,
+	helpers::{
+		index_to_number,
+		normalize_index,
+		range_len,
+		range_values,
+		unwrap_list,
+		unwrap_string,
+	},
 	types::{
 		InterpreterFunction,
 		InterpreterValue,
@@ -19,9 +26,10 @@ use std::{
 	io::{self, Write},
 	rc::Rc,
 };
+use unicode_segmentation::UnicodeSegmentation;
 
 
-pub const NATIVE_FUNCTION_NAMES: [&str; 15] = [
+pub const NATIVE_FUNCTION_NAMES: [&str; 19] = [
 	"str",
 	"typeof",
 	"number",
@@ -37,6 +45,10 @@ pub const NATIVE_FUNCTION_NAMES: [&str; 15] = [
 	"has",
 	"unset",
 	"read",
+	"chars",
+	"char_len",
+	"char_at",
+	"graphemes",
 ];
 
 struct FunctionDefinition<'a> {
@@ -83,42 +95,50 @@ fn native_number(
 		InterpreterValue::Char(c) => Ok(InterpreterValue::Number(
 			c.to_digit(10).map_or(f64::NAN, std::convert::Into::into),
 		)),
-		_ => Err(RuntimeError {
-			message: format!("Can't parse {} to number", input.human_type()),
-			token: keyword.clone(),
-		}),
+		_ => Err(RuntimeError::new(
+			format!("Can't parse {} to number", input.human_type()),
+			keyword.clone(),
+		)),
 	}
 }
 
 fn native_len(
 	keyword: &Token,
-	_env: &InterpreterEnvironment,
+	env: &InterpreterEnvironment,
 	args: &[InterpreterValue],
 ) -> Result<InterpreterValue, RuntimeError> {
 	match &args[0] {
-		InterpreterValue::String(s) => try_exact_convert!(s.len(), usize, f64)
-			.map_err(|_| RuntimeError {
-				message: format!("Cannot conver from {}_usize to f64", s.len(),),
-				token: keyword.clone(),
-			})
-			.map(InterpreterValue::Number),
+		InterpreterValue::String(s) => {
+			let len = if env.program().uses_chars(keyword) {
+				env.program().char_offsets(s).len()
+			} else {
+				s.len()
+			};
+
+			index_to_number(len, keyword)
+		}
 		InterpreterValue::List(l) => {
 			let l_borrow = l.borrow();
 
 			try_exact_convert!(l_borrow.len(), usize, f64)
-				.map_err(|_| RuntimeError {
-					message: format!(
-						"Cannot conver from {}_usize to f64",
-						l_borrow.len(),
-					),
-					token: keyword.clone(),
+				.map_err(|_| {
+					RuntimeError::new(
+						format!(
+							"Cannot conver from {}_usize to f64",
+							l_borrow.len(),
+						),
+						keyword.clone(),
+					)
 				})
 				.map(InterpreterValue::Number)
 		}
-		_ => Err(RuntimeError {
-			message: format!("Can't get length of {}", &args[0].human_type()),
-			token: keyword.clone(),
-		}),
+		InterpreterValue::Range { start, end } => {
+			index_to_number(range_len(*start, *end), keyword)
+		}
+		_ => Err(RuntimeError::new(
+			format!("Can't get length of {}", &args[0].human_type()),
+			keyword.clone(),
+		)),
 	}
 }
 
@@ -143,10 +163,22 @@ fn native_expand(
 
 			Ok(InterpreterValue::List(Rc::new(RefCell::new(keys))))
 		}
-		_ => Err(RuntimeError {
-			message: format!("Can't use expand on {}", val.human_type()),
-			token: keyword.clone(),
-		}),
+		InterpreterValue::Module(exports) => {
+			let keys = exports
+				.keys()
+				.cloned()
+				.map(|k| InterpreterValue::String(k.into()))
+				.collect();
+
+			Ok(InterpreterValue::List(Rc::new(RefCell::new(keys))))
+		}
+		InterpreterValue::Range { start, end } => Ok(InterpreterValue::List(
+			Rc::new(RefCell::new(range_values(*start, *end).collect())),
+		)),
+		_ => Err(RuntimeError::new(
+			format!("Can't use expand on {}", val.human_type()),
+			keyword.clone(),
+		)),
 	}
 }
 
@@ -192,13 +224,10 @@ fn native_from_chars(
 			if let InterpreterValue::Char(c) = v {
 				Ok(*c)
 			} else {
-				Err(RuntimeError {
-					message: format!(
-						"Cannot convert from {} to char",
-						v.human_type()
-					),
-					token: keyword.clone(),
-				})
+				Err(RuntimeError::new(
+					format!("Cannot convert from {} to char", v.human_type()),
+					keyword.clone(),
+				))
 			}
 		})
 		.collect::<Result<String, RuntimeError>>()?;
@@ -254,10 +283,10 @@ fn native_is_nan(
 	if let InterpreterValue::Number(n) = value {
 		Ok(n.is_nan().into())
 	} else {
-		Err(RuntimeError {
-			message: format!("Cannot use is_nan on {}", value.human_type()),
-			token: keyword.clone(),
-		})
+		Err(RuntimeError::new(
+			format!("Cannot use is_nan on {}", value.human_type()),
+			keyword.clone(),
+		))
 	}
 }
 
@@ -271,10 +300,10 @@ fn native_floor(
 	if let InterpreterValue::Number(n) = value {
 		Ok(InterpreterValue::Number(n.floor()))
 	} else {
-		Err(RuntimeError {
-			message: format!("Cannot use floor on {}", value.human_type()),
-			token: keyword.clone(),
-		})
+		Err(RuntimeError::new(
+			format!("Cannot use floor on {}", value.human_type()),
+			keyword.clone(),
+		))
 	}
 }
 
@@ -288,10 +317,10 @@ fn native_ceil(
 	if let InterpreterValue::Number(n) = value {
 		Ok(InterpreterValue::Number(n.ceil()))
 	} else {
-		Err(RuntimeError {
-			message: format!("Cannot use ceil on {}", value.human_type()),
-			token: keyword.clone(),
-		})
+		Err(RuntimeError::new(
+			format!("Cannot use ceil on {}", value.human_type()),
+			keyword.clone(),
+		))
 	}
 }
 
@@ -309,6 +338,9 @@ fn native_has(
 
 			Ok(borrowed_props.contains_key(&value.to_string()).into())
 		}
+		(InterpreterValue::Module(exports), _) => {
+			Ok(exports.contains_key(&value.to_string()).into())
+		}
 		(InterpreterValue::List(l), _) => {
 			let l_borrow = l.borrow();
 
@@ -320,14 +352,20 @@ fn native_has(
 		(InterpreterValue::String(s), InterpreterValue::Char(c)) => {
 			Ok(s.contains(*c).into())
 		}
-		_ => Err(RuntimeError {
-			message: format!(
+		(
+			InterpreterValue::Range { start, end },
+			InterpreterValue::Number(n),
+		) => Ok(try_exact_convert!(*n, f64, i64)
+			.is_ok_and(|n| (*start..*end).contains(&n))
+			.into()),
+		_ => Err(RuntimeError::new(
+			format!(
 				"Cannot use has with {} and {}",
 				searchee.human_type(),
 				value.human_type()
 			),
-			token: keyword.clone(),
-		}),
+			keyword.clone(),
+		)),
 	}
 }
 
@@ -348,14 +386,14 @@ fn native_unset(
 
 			Ok(borrowed_props.remove(&**s).unwrap_or(InterpreterValue::Nil))
 		}
-		_ => Err(RuntimeError {
-			message: format!(
+		_ => Err(RuntimeError::new(
+			format!(
 				"Cannot use unset with {} and {}",
 				map.human_type(),
 				key.human_type()
 			),
-			token: keyword.clone(),
-		}),
+			keyword.clone(),
+		)),
 	}
 }
 
@@ -372,18 +410,14 @@ fn native_read(
 
 	print!("{}", to_print);
 
-	io::stdout().flush().map_err(|e| RuntimeError {
-		message: e.to_string(),
-		token: keyword.clone(),
-	})?;
+	io::stdout()
+		.flush()
+		.map_err(|e| RuntimeError::new(e.to_string(), keyword.clone()))?;
 
 	let mut buffer = String::new();
 	io::stdin()
 		.read_line(&mut buffer)
-		.map_err(|e| RuntimeError {
-			message: e.to_string(),
-			token: keyword.clone(),
-		})?;
+		.map_err(|e| RuntimeError::new(e.to_string(), keyword.clone()))?;
 
 	Ok(InterpreterValue::String(buffer.into()))
 }
@@ -406,6 +440,65 @@ fn declarator(env: &InterpreterEnvironment, funs: &[FunctionDefinition]) {
 	}
 }
 
+fn native_chars(
+	keyword: &Token,
+	_env: &InterpreterEnvironment,
+	args: &[InterpreterValue],
+) -> Result<InterpreterValue, RuntimeError> {
+	let s = unwrap_string(&args[0], keyword, 0)?;
+
+	Ok(InterpreterValue::List(Rc::new(RefCell::new(
+		s.chars().map(InterpreterValue::Char).collect(),
+	))))
+}
+
+fn native_char_len(
+	keyword: &Token,
+	_env: &InterpreterEnvironment,
+	args: &[InterpreterValue],
+) -> Result<InterpreterValue, RuntimeError> {
+	let s = unwrap_string(&args[0], keyword, 0)?;
+
+	index_to_number(s.chars().count(), keyword)
+}
+
+fn native_char_at(
+	keyword: &Token,
+	_env: &InterpreterEnvironment,
+	args: &[InterpreterValue],
+) -> Result<InterpreterValue, RuntimeError> {
+	let s = unwrap_string(&args[0], keyword, 0)?;
+
+	let n = if let InterpreterValue::Number(n) = &args[1] {
+		*n
+	} else {
+		return Err(RuntimeError::new(
+			"Argument 1 must be of type number".into(),
+			keyword.clone(),
+		));
+	};
+
+	let index = normalize_index(n, keyword, s.chars().count())?;
+
+	Ok(InterpreterValue::Char(s.chars().nth(index).unwrap_or_else(
+		|| unreachable!("Index is out of the string's bounds \u{1f914}"),
+	)))
+}
+
+fn native_graphemes(
+	keyword: &Token,
+	_env: &InterpreterEnvironment,
+	args: &[InterpreterValue],
+) -> Result<InterpreterValue, RuntimeError> {
+	let s = unwrap_string(&args[0], keyword, 0)?;
+
+	Ok(InterpreterValue::List(Rc::new(RefCell::new(
+		s.graphemes(true)
+			.map(|g| InterpreterValue::String(g.into()))
+			.collect(),
+	))))
+}
+
 pub fn declare(env: &InterpreterEnvironment) {
 	declarator(
 		env,
@@ -485,6 +578,26 @@ pub fn declare(env: &InterpreterEnvironment) {
 				arity: 1,
 				fun: native_read,
 			},
+			FunctionDefinition {
+				name: NATIVE_FUNCTION_NAMES[15],
+				arity: 1,
+				fun: native_chars,
+			},
+			FunctionDefinition {
+				name: NATIVE_FUNCTION_NAMES[16],
+				arity: 1,
+				fun: native_char_len,
+			},
+			FunctionDefinition {
+				name: NATIVE_FUNCTION_NAMES[17],
+				arity: 2,
+				fun: native_char_at,
+			},
+			FunctionDefinition {
+				name: NATIVE_FUNCTION_NAMES[18],
+				arity: 1,
+				fun: native_graphemes,
+			},
 		],
 	);
 }
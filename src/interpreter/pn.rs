@@ -1,4 +1,7 @@
-use crate::ast::expr::{Expr, LiteralValue};
+use crate::{
+	ast::expr::{Expr, LiteralValue, MatchArm, Pattern, PatternType},
+	token::Token,
+};
 
 
 #[allow(dead_code)]
@@ -21,11 +24,48 @@ pub fn stringify_tree(expr: &Expr) -> String {
 			LiteralValue::Char(c) => format!("{:?}", c),
 		},
 		Expr::Identifier(v) => v.name.token_type.to_string(),
-		Expr::Assignment(v) => pn_gen(&format!("= {}", v.name), &[&v.value]),
+		Expr::Assignment(v) => pn_gen(
+			&format!(
+				"{}= {}",
+				v.operator
+					.as_ref()
+					.map_or_else(String::new, |o| o.token_type.to_string()),
+				v.name
+			),
+			&[&v.value],
+		),
+		Expr::DestructuringAssignment(v) => {
+			pn_gen(&format!("= {}", stringify_pattern(&v.pattern)), &[&v.value])
+		}
 		Expr::Call(v) => pn_gen(
 			&format!("call {}", stringify_tree(&v.calee)),
 			v.arguments.iter().collect::<Vec<&Expr>>().as_slice(),
 		),
+		Expr::Template(v) => pn_gen(
+			"template",
+			v.parts.iter().collect::<Vec<&Expr>>().as_slice(),
+		),
+		Expr::Conditional(v) => {
+			let mut exprs = vec![&*v.condition, &*v.then];
+
+			if let Some(otherwise) = &v.otherwise {
+				exprs.push(otherwise);
+			}
+
+			pn_gen("if", &exprs)
+		}
+		// statements aren't expressions, so only the value gets printed
+		Expr::Sequence(v) => v.value.as_ref().map_or_else(
+			|| "(sequence ...)".into(),
+			|value| pn_gen("sequence ...", &[value]),
+		),
+		Expr::Match(v) => {
+			let arms = v.arms.iter().map(stringify_arm).collect::<String>();
+
+			format!("(match {}{})", stringify_tree(&v.value), arms)
+		}
+		Expr::Spread(v) => pn_gen("...", &[&v.value]),
+		Expr::OptionalChain(v) => pn_gen("?", &[&v.expression]),
 		// TODO: implement these XD
 		Expr::Function(_v) => unimplemented!(),
 		Expr::Get(_v) => unimplemented!(),
@@ -36,6 +76,70 @@ pub fn stringify_tree(expr: &Expr) -> String {
 	}
 }
 
+fn stringify_arm(arm: &MatchArm) -> String {
+	let guard = arm.guard.as_ref().map_or_else(String::new, |guard| {
+		format!(" (if {})", stringify_tree(guard))
+	});
+
+	format!(
+		" (=> {}{} {})",
+		stringify_pattern(&arm.pattern),
+		guard,
+		stringify_tree(&arm.body)
+	)
+}
+
+fn stringify_pattern(pattern: &Pattern) -> String {
+	match pattern {
+		Pattern::Literal(v) => stringify_tree(v),
+		Pattern::Binding(binding) => stringify_binding(binding.as_ref()),
+		Pattern::Type { kind, binding } => {
+			let kind = match kind {
+				PatternType::Builtin(name) => name.to_string(),
+				PatternType::Class(class) => stringify_tree(class),
+			};
+
+			format!("({} {})", kind, stringify_binding(binding.as_ref()))
+		}
+		Pattern::List {
+			before,
+			rest,
+			after,
+		} => {
+			let mut items: Vec<String> =
+				before.iter().map(stringify_pattern).collect();
+
+			if let Some(rest) = rest {
+				items.push(format!("...{}", stringify_pattern(rest)));
+			}
+
+			items.extend(after.iter().map(stringify_pattern));
+
+			format!("[{}]", items.join(", "))
+		}
+		Pattern::Object { class, properties } => {
+			let properties = properties
+				.iter()
+				.map(|p| {
+					format!("{}: {}", p.key, stringify_pattern(&p.pattern))
+				})
+				.collect::<Vec<String>>()
+				.join(", ");
+
+			class.as_ref().map_or_else(
+				|| format!("{{ {} }}", properties),
+				|class| {
+					format!("{} {{ {} }}", stringify_tree(class), properties)
+				},
+			)
+		}
+	}
+}
+
+fn stringify_binding(binding: Option<&Token>) -> String {
+	binding.map_or_else(|| "_".into(), |b| b.token_type.to_string())
+}
+
 fn pn_gen(name: &str, exprs: &[&Expr]) -> String {
 	let mut res = format!("({}", name);
 
@@ -1,30 +1,55 @@
 use super::{
 	env::InterpreterEnvironment,
-	helpers::{assume_identifier, construct_lox_defined_function},
-	interpret::eval_expression,
-	types::{InterpreterValue, RuntimeError, StmtResult},
+	expressions::{
+		execute_call,
+		find_method,
+		is_instance_of,
+		pattern_class,
+		pattern_property,
+	},
+	helpers::{
+		assume_identifier,
+		construct_lox_defined_function,
+		index_to_number,
+		range_values,
+	},
+	interpret::{eval_expression, eval_statement},
+	types::{
+		ClassMembers,
+		EnumDeclaration,
+		EnumVariant,
+		InterpreterValue,
+		RuntimeError,
+		StmtResult,
+	},
 };
 use crate::{
 	ast::{
-		expr::Expr,
+		expr::{Expr, Pattern},
 		stmt::{
 			BlockValue,
 			BreakValue,
 			ClassValue,
 			ContinueValue,
 			DeclarationValue,
+			DestructureValue,
+			EnumValue,
 			ExpressionValue,
+			ForInValue,
 			ForValue,
 			IfValue,
 			PrintValue,
 			ReturnValue,
 			Stmt,
+			ThrowValue,
+			TryValue,
 		},
 	},
 	env::{DeclaredValue, EnvironmentWrapper},
+	token::Token,
 };
 
-use std::{collections::HashMap, rc::Rc};
+use std::{cell::RefCell, collections::HashMap, rc::Rc};
 
 
 #[inline]
@@ -84,6 +109,155 @@ where
 	Ok(StmtResult::Noop)
 }
 
+pub fn destructure_statement(
+	v: &DestructureValue,
+	env: &InterpreterEnvironment,
+) -> Result<StmtResult<InterpreterValue>, RuntimeError> {
+	let value = eval_expression(&v.initializer, env)?;
+
+	destructure(&v.pattern, &value, &v.blame, env, &mut |binding, value| {
+		env.declare(
+			assume_identifier(binding).to_owned(),
+			DeclaredValue {
+				mutable: v.mutable,
+				value,
+			},
+		);
+
+		Ok(())
+	})?;
+
+	Ok(StmtResult::Noop)
+}
+
+/// Hands pattern's bindings with their values to `bind`, erroring if the
+/// value doesn't have the pattern's shape
+#[allow(clippy::too_many_lines)]
+pub fn destructure<F>(
+	pattern: &Pattern,
+	value: &InterpreterValue,
+	blame: &Token,
+	env: &InterpreterEnvironment,
+	bind: &mut F,
+) -> Result<(), RuntimeError>
+where
+	F: FnMut(&Token, InterpreterValue) -> Result<(), RuntimeError>,
+{
+	let mismatch = |message: String| RuntimeError::new(message, blame.clone());
+
+	match pattern {
+		Pattern::Binding(binding) => {
+			if let Some(binding) = binding {
+				bind(binding, value.clone())?;
+			}
+
+			Ok(())
+		}
+		Pattern::List {
+			before,
+			rest,
+			after,
+		} => {
+			let elements = if let InterpreterValue::List(l) = value {
+				l.borrow().clone()
+			} else {
+				return Err(mismatch(format!(
+					"Cannot destructure {} as a list",
+					value.human_type()
+				)));
+			};
+
+			let fixed_len = before.len() + after.len();
+
+			if rest.is_none() && elements.len() != fixed_len {
+				return Err(mismatch(format!(
+					"Expected a list of {} elements, got {}",
+					fixed_len,
+					elements.len()
+				)));
+			} else if elements.len() < fixed_len {
+				return Err(mismatch(format!(
+					"Expected a list of at least {} elements, got {}",
+					fixed_len,
+					elements.len()
+				)));
+			}
+
+			let after_start = elements.len() - after.len();
+
+			let fixed_patterns = before
+				.iter()
+				.zip(&elements)
+				.chain(after.iter().zip(&elements[after_start..]));
+
+			for (element_pattern, element) in fixed_patterns {
+				destructure(element_pattern, element, blame, env, bind)?;
+			}
+
+			if let Some(rest) = rest {
+				let rest_value = InterpreterValue::List(Rc::new(RefCell::new(
+					elements[before.len()..after_start].to_vec(),
+				)));
+
+				destructure(rest, &rest_value, blame, env, bind)?;
+			}
+
+			Ok(())
+		}
+		Pattern::Object { class, properties } => {
+			if !matches!(
+				value,
+				InterpreterValue::Instance { .. }
+					| InterpreterValue::Module(_)
+					| InterpreterValue::Variant { .. }
+			) {
+				return Err(mismatch(format!(
+					"Cannot destructure {} as an object",
+					value.human_type()
+				)));
+			}
+
+			if let Some(class) = class {
+				let class = pattern_class(class, env)?;
+
+				if !is_instance_of(value, &class) {
+					return Err(mismatch(format!(
+						"Expected an instance of {}, got {}",
+						class.repr(true),
+						value.repr(true)
+					)));
+				}
+			}
+
+			for property in properties {
+				if let Some(property_value) =
+					pattern_property(value, &property.key)
+				{
+					destructure(
+						&property.pattern,
+						&property_value,
+						blame,
+						env,
+						bind,
+					)?;
+				} else {
+					return Err(mismatch(format!(
+						"Cannot destructure missing property `{}`",
+						property.key
+					)));
+				}
+			}
+
+			Ok(())
+		}
+		Pattern::Literal(_) | Pattern::Type { .. } => {
+			unreachable!(
+				"Destructuring patterns consist of bindings, lists and objects"
+			)
+		}
+	}
+}
+
 #[inline]
 pub fn block_statement<E, T>(
 	stmts_evaluator: fn(&[Stmt], &E) -> Result<StmtResult<T>, RuntimeError>,
@@ -138,8 +312,14 @@ where
 			let e = stmt_evaluator(&v.body, env)?;
 
 			match e {
-				StmtResult::Break(_) => break,
-				StmtResult::Continue(_) => {
+				StmtResult::Break { label, .. }
+					if targets_loop(label.as_ref(), v.label.as_ref()) =>
+				{
+					break
+				}
+				StmtResult::Continue { label, .. }
+					if targets_loop(label.as_ref(), v.label.as_ref()) =>
+				{
 					if let Some(c) = &v.closer {
 						stmt_evaluator(c, env)?;
 					}
@@ -147,7 +327,8 @@ where
 					continue;
 				}
 				StmtResult::Noop => (),
-				StmtResult::Return { .. } => {
+				// returns and jumps out of the enclosing loops
+				_ => {
 					return Ok(e);
 				}
 			}
@@ -161,8 +342,14 @@ where
 			let e = stmt_evaluator(&v.body, env)?;
 
 			match e {
-				StmtResult::Break(_) => break,
-				StmtResult::Continue(_) => {
+				StmtResult::Break { label, .. }
+					if targets_loop(label.as_ref(), v.label.as_ref()) =>
+				{
+					break
+				}
+				StmtResult::Continue { label, .. }
+					if targets_loop(label.as_ref(), v.label.as_ref()) =>
+				{
 					if let Some(c) = &v.closer {
 						stmt_evaluator(c, env)?;
 					}
@@ -170,7 +357,8 @@ where
 					continue;
 				}
 				StmtResult::Noop => (),
-				StmtResult::Return { .. } => {
+				// returns and jumps out of the enclosing loops
+				_ => {
 					return Ok(e);
 				}
 			}
@@ -184,6 +372,290 @@ where
 	Ok(StmtResult::Noop)
 }
 
+pub fn for_in_statement(
+	v: &ForInValue,
+	env: &InterpreterEnvironment,
+) -> Result<StmtResult<InterpreterValue>, RuntimeError> {
+	let iterable = eval_expression(&v.iterable, env)?;
+
+	match &iterable {
+		InterpreterValue::List(l) => {
+			let mut index = 0;
+
+			// the body can modify the list, so we can't hold the borrow (a
+			// `while let` would hold it for the whole body)
+			loop {
+				let item = l.borrow().get(index).cloned();
+
+				let Some(item) = item else { break };
+
+				let key = index_to_number(index, &v.blame)?;
+
+				if let Some(res) = for_in_iteration(v, env, key, item)? {
+					return Ok(res);
+				}
+
+				index += 1;
+			}
+		}
+		InterpreterValue::String(s) => {
+			for (index, c) in s.chars().enumerate() {
+				let key = index_to_number(index, &v.blame)?;
+
+				let value = InterpreterValue::Char(c);
+
+				if let Some(res) = for_in_iteration(v, env, key, value)? {
+					return Ok(res);
+				}
+			}
+		}
+		InterpreterValue::Range { start, end } => {
+			for (index, value) in range_values(*start, *end).enumerate() {
+				let key = index_to_number(index, &v.blame)?;
+
+				if let Some(res) = for_in_iteration(v, env, key, value)? {
+					return Ok(res);
+				}
+			}
+		}
+		InterpreterValue::Instance {
+			class: None,
+			properties,
+		} => {
+			// sorted, so that the order doesn't change between runs
+			let mut keys: Vec<String> =
+				properties.borrow().keys().cloned().collect();
+			keys.sort();
+
+			for key in keys {
+				// skipping properties removed by the body
+				let value = if let Some(value) = properties.borrow().get(&key) {
+					value.clone()
+				} else {
+					continue;
+				};
+
+				let key = InterpreterValue::String(key.into());
+
+				let (key, value) = if v.key.is_some() {
+					(key, value)
+				} else {
+					// iterating over an object with one name gives the keys
+					(InterpreterValue::Nil, key)
+				};
+
+				if let Some(res) = for_in_iteration(v, env, key, value)? {
+					return Ok(res);
+				}
+			}
+		}
+		InterpreterValue::Instance {
+			class: Some(class), ..
+		} => {
+			let methods = find_method("has_next", class, &iterable, &v.blame)
+				.and_then(|has_next| {
+					Ok((
+						has_next,
+						find_method("next", class, &iterable, &v.blame)?,
+					))
+				});
+
+			let (has_next, next) = methods.map_err(|_| {
+				RuntimeError::new(
+					format!(
+						"Cannot iterate over {}, it has to implement \
+						 `has_next()` and `next()`",
+						iterable
+					),
+					v.blame.clone(),
+				)
+			})?;
+
+			let mut index = 0;
+
+			while execute_call(&has_next, &[], &v.blame, env)?
+				== InterpreterValue::True
+			{
+				let key = index_to_number(index, &v.blame)?;
+
+				let value = execute_call(&next, &[], &v.blame, env)?;
+
+				if let Some(res) = for_in_iteration(v, env, key, value)? {
+					return Ok(res);
+				}
+
+				index += 1;
+			}
+		}
+		_ => {
+			return Err(RuntimeError::new(
+				format!("Cannot iterate over {}", iterable.human_type()),
+				v.blame.clone(),
+			));
+		}
+	}
+
+	Ok(StmtResult::Noop)
+}
+
+/// Runs the for-in body with loop variables declared in a fresh scope
+///
+/// returns the result to end the loop with, or `None` to keep going
+fn for_in_iteration(
+	v: &ForInValue,
+	env: &InterpreterEnvironment,
+	key: InterpreterValue,
+	value: InterpreterValue,
+) -> Result<Option<StmtResult<InterpreterValue>>, RuntimeError> {
+	let body = if let Some(body) = &v.body {
+		body
+	} else {
+		return Ok(None);
+	};
+
+	let iteration_env = env.fork();
+
+	if let Some(key_name) = &v.key {
+		iteration_env.declare(
+			assume_identifier(key_name).to_owned(),
+			DeclaredValue {
+				mutable: false,
+				value: key,
+			},
+		);
+	}
+
+	iteration_env.declare(
+		assume_identifier(&v.value).to_owned(),
+		DeclaredValue {
+			mutable: false,
+			value,
+		},
+	);
+
+	match eval_statement(body, &iteration_env)? {
+		StmtResult::Break { label, .. }
+			if targets_loop(label.as_ref(), v.label.as_ref()) =>
+		{
+			Ok(Some(StmtResult::Noop))
+		}
+		StmtResult::Continue { label, .. }
+			if targets_loop(label.as_ref(), v.label.as_ref()) =>
+		{
+			Ok(None)
+		}
+		StmtResult::Noop => Ok(None),
+		e => Ok(Some(e)),
+	}
+}
+
+/// Tells if a `break` or `continue` is meant for a loop - unlabeled ones
+/// target the innermost loop, labeled ones the loop with the same label
+fn targets_loop(label: Option<&Token>, loop_label: Option<&Token>) -> bool {
+	label.is_none_or(|label| {
+		loop_label.is_some_and(|loop_label| {
+			assume_identifier(label) == assume_identifier(loop_label)
+		})
+	})
+}
+
+pub fn throw_statement(
+	v: &ThrowValue,
+	env: &InterpreterEnvironment,
+) -> Result<StmtResult<InterpreterValue>, RuntimeError> {
+	let value = eval_expression(&v.expression, env)?;
+
+	Err(RuntimeError {
+		message: format!("Uncaught {}", value.repr(true)),
+		token: v.keyword.clone(),
+		thrown: Some(Box::new(value)),
+	})
+}
+
+pub fn try_statement(
+	v: &TryValue,
+	env: &InterpreterEnvironment,
+) -> Result<StmtResult<InterpreterValue>, RuntimeError> {
+	let result = v
+		.body
+		.as_ref()
+		.map_or(Ok(StmtResult::Noop), |body| eval_statement(body, env));
+
+	let result = match (result, &v.catch) {
+		(Err(error), Some(catch)) => {
+			let catch_env = env.fork();
+
+			if let Some(name) = &catch.name {
+				catch_env.declare(
+					assume_identifier(name).to_owned(),
+					DeclaredValue {
+						mutable: false,
+						value: caught_value(error, env)?,
+					},
+				);
+			}
+
+			catch.body.as_ref().map_or(Ok(StmtResult::Noop), |body| {
+				eval_statement(body, &catch_env)
+			})
+		}
+		(result, _) => result,
+	};
+
+	if let Some(finally) = &v.finally {
+		// jumps and errors from `finally` take precedence over the ones
+		// from `try` and `catch`
+		let finally_result = eval_statement(finally, env)?;
+
+		if !matches!(finally_result, StmtResult::Noop) {
+			return Ok(finally_result);
+		}
+	}
+
+	result
+}
+
+/// The value bound in `catch` - either the thrown value, or an object
+/// describing an error raised by the interpreter
+fn caught_value(
+	error: RuntimeError,
+	env: &InterpreterEnvironment,
+) -> Result<InterpreterValue, RuntimeError> {
+	if let Some(thrown) = error.thrown {
+		return Ok(*thrown);
+	}
+
+	let location = error.token.location;
+
+	let files = env.files().borrow();
+	let file = files.get(location.file);
+	let line = file.line(location.byte_offset);
+
+	let mut properties = HashMap::new();
+
+	properties.insert(
+		"line".to_owned(),
+		index_to_number(line.number, &error.token)?,
+	);
+	properties.insert(
+		"column".to_owned(),
+		index_to_number(location.byte_offset - line.start + 1, &error.token)?,
+	);
+	properties.insert(
+		"file".to_owned(),
+		InterpreterValue::String(file.name.clone()),
+	);
+	properties.insert(
+		"message".to_owned(),
+		InterpreterValue::String(error.message.into()),
+	);
+
+	Ok(InterpreterValue::Instance {
+		class: None,
+		properties: Rc::new(RefCell::new(properties)),
+	})
+}
+
 #[inline]
 pub fn return_statement<E>(
 	expr_evaluator: fn(&Expr, &E) -> Result<InterpreterValue, RuntimeError>,
@@ -204,12 +676,45 @@ where
 
 #[inline]
 pub fn break_statement<T>(v: &BreakValue) -> StmtResult<T> {
-	StmtResult::Break(v.keyword.clone())
+	StmtResult::Break {
+		keyword: v.keyword.clone(),
+		label: v.label.clone(),
+	}
 }
 
 #[inline]
 pub fn continue_statement<T>(v: &ContinueValue) -> StmtResult<T> {
-	StmtResult::Continue(v.keyword.clone())
+	StmtResult::Continue {
+		keyword: v.keyword.clone(),
+		label: v.label.clone(),
+	}
+}
+
+/// Constructs class methods, keyed by their names
+fn method_map(
+	methods: &[Expr],
+	class_env: &InterpreterEnvironment,
+) -> HashMap<String, InterpreterValue> {
+	methods
+		.iter()
+		.map(|method| {
+			let fv = if let Expr::Function(v) = method {
+				v
+			} else {
+				unreachable!(
+					"Method should be a function expression. Parser fucked up"
+				)
+			};
+
+			let name =
+				assume_identifier(fv.name.as_ref().expect("Method name"));
+
+			(
+				name.to_owned(),
+				construct_lox_defined_function(fv, class_env),
+			)
+		})
+		.collect()
 }
 
 pub fn class_statement(
@@ -222,13 +727,10 @@ pub fn class_statement(
 		let evaluated = eval_expression(expr, env)?;
 
 		if !matches!(evaluated, InterpreterValue::Class { .. }) {
-			return Err(RuntimeError {
-				message: format!(
-					"Cannot inherit from {}",
-					evaluated.human_type()
-				),
-				token: v.name.clone(),
-			});
+			return Err(RuntimeError::new(
+				format!("Cannot inherit from {}", evaluated.human_type()),
+				v.name.clone(),
+			));
 		}
 
 		let superclass = eval_expression(expr, env)?;
@@ -250,42 +752,96 @@ pub fn class_statement(
 
 	let class_env = super_env.as_ref().unwrap_or(env);
 
-	let mut methods = HashMap::new();
-
-	let mut constructor = None;
+	let mut methods = method_map(&v.methods, class_env);
+
+	let constructor = methods.remove("constructor").map(Rc::new);
+
+	let members = Rc::new(ClassMembers {
+		static_methods: method_map(&v.static_methods, class_env),
+		static_fields: RefCell::default(),
+		getters: method_map(&v.getters, class_env),
+		setters: method_map(&v.setters, class_env),
+		fields: Rc::clone(&v.fields),
+		env: class_env.clone(),
+	});
+
+	let class = InterpreterValue::Class {
+		superclass,
+		constructor,
+		name: Rc::from(name),
+		methods: Rc::new(methods),
+		members: Rc::clone(&members),
+	};
 
-	for method in &v.methods {
-		let fv = if let Expr::Function(v) = method {
-			v
-		} else {
-			unreachable!(
-				"Method should be a function expression. Parser fucked up"
-			)
-		};
+	env.declare(
+		name.to_owned(),
+		DeclaredValue {
+			mutable: false,
+			value: class.clone(),
+		},
+	);
 
-		let fun = construct_lox_defined_function(fv, class_env);
+	// static fields are initialized once the class is declared, so that they
+	// can refer to it, with `this` bound to the class
+	if v.fields.iter().any(|field| field.is_static) {
+		let static_env = class_env.fork();
 
-		let name = assume_identifier(fv.name.as_ref().expect("Method name"));
+		static_env.declare(
+			"this".into(),
+			DeclaredValue {
+				mutable: false,
+				value: class,
+			},
+		);
 
-		if name == "constructor" {
-			constructor = Some(Rc::new(fun));
-		} else {
-			methods.insert(name.to_owned(), fun);
+		for field in v.fields.iter().filter(|field| field.is_static) {
+			let value = field
+				.initializer
+				.as_ref()
+				.map_or(Ok(InterpreterValue::Nil), |initializer| {
+					eval_expression(initializer, &static_env)
+				})?;
+
+			members
+				.static_fields
+				.borrow_mut()
+				.insert(assume_identifier(&field.name).to_owned(), value);
 		}
 	}
 
+	Ok(StmtResult::Noop)
+}
+
+pub fn enum_statement(
+	v: &EnumValue,
+	env: &InterpreterEnvironment,
+) -> StmtResult<InterpreterValue> {
+	let name = assume_identifier(&v.name);
+
+	let variants = v
+		.variants
+		.iter()
+		.map(|variant| EnumVariant {
+			name: Rc::from(assume_identifier(&variant.name)),
+			fields: variant.fields.as_ref().map(|fields| {
+				fields
+					.iter()
+					.map(|field| Rc::from(assume_identifier(field)))
+					.collect()
+			}),
+		})
+		.collect();
+
 	env.declare(
 		name.to_owned(),
 		DeclaredValue {
 			mutable: false,
-			value: InterpreterValue::Class {
-				superclass,
-				constructor,
+			value: InterpreterValue::Enum(Rc::new(EnumDeclaration {
 				name: Rc::from(name),
-				methods: Rc::new(methods),
-			},
+				variants,
+			})),
 		},
 	);
 
-	Ok(StmtResult::Noop)
+	StmtResult::Noop
 }
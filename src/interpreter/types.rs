@@ -1,6 +1,6 @@
 use super::env::InterpreterEnvironment;
 use crate::{
-	ast::expr::FunctionValue,
+	ast::{expr::FunctionValue, stmt::Field},
 	runner::DescribableError,
 	token::{Location, Token},
 };
@@ -13,6 +13,18 @@ const MAX_LIST_VALUES_PRINT: usize = 100;
 pub struct RuntimeError {
 	pub message: String,
 	pub token: Token,
+	/// the value of a `throw`, `None` for errors raised by the interpreter
+	pub thrown: Option<Box<InterpreterValue>>,
+}
+
+impl RuntimeError {
+	pub const fn new(message: String, token: Token) -> Self {
+		Self {
+			message,
+			token,
+			thrown: None,
+		}
+	}
 }
 
 impl DescribableError for RuntimeError {
@@ -40,8 +52,23 @@ pub enum InterpreterValue {
 		constructor: Option<Rc<InterpreterValue>>,
 		name: Rc<str>,
 		methods: Rc<HashMap<String, InterpreterValue>>,
+		members: Rc<ClassMembers>,
+	},
+	Enum(Rc<EnumDeclaration>),
+	/// Exports of an imported module, read-only as every importer shares them
+	Module(Rc<HashMap<String, InterpreterValue>>),
+	/// A value of one of the enum's variants, immutable and compared by value
+	Variant {
+		enumeration: Rc<EnumDeclaration>,
+		index: usize,
+		values: Rc<Vec<InterpreterValue>>,
 	},
 	List(Rc<RefCell<Vec<InterpreterValue>>>),
+	/// A lazy sequence of integers from `start` up to, but excluding `end`
+	Range {
+		start: i64,
+		end: i64,
+	},
 	String(Rc<str>),
 	Number(f64),
 	Char(char),
@@ -57,9 +84,13 @@ impl InterpreterValue {
 			InterpreterValue::Instance { .. } => "class instance",
 			InterpreterValue::Function { .. } => "function",
 			InterpreterValue::Class { .. } => "class",
+			InterpreterValue::Enum(_) => "enum",
+			InterpreterValue::Module(_) => "module",
+			InterpreterValue::Variant { .. } => "variant",
 			InterpreterValue::String(_) => "string",
 			InterpreterValue::Number(_) => "number",
 			InterpreterValue::List(_) => "list",
+			InterpreterValue::Range { .. } => "range",
 			InterpreterValue::Char(_) => "char",
 			InterpreterValue::Nil => "nil",
 		}
@@ -101,35 +132,40 @@ impl InterpreterValue {
 					return format!("instance of {}", class);
 				}
 
-				let take_amount =
-					if nested { 0 } else { MAX_LIST_VALUES_PRINT };
-
-				let p_borrow = properties.borrow();
-
-				let mut obj_repr = String::from("{ ");
-
-				obj_repr += &p_borrow
-					.iter()
-					.take(take_amount)
-					.map(|(k, v)| format!("\n\t{}: {},", k, v.repr(true)))
-					.collect::<String>();
-
-				let key_num = p_borrow.len();
+				properties_repr(&properties.borrow(), nested)
+			}
+			InterpreterValue::Module(exports) => {
+				format!("module {}", properties_repr(exports, nested))
+			}
+			InterpreterValue::Range { start, end } => {
+				format!("{}..{}", start, end)
+			}
+			InterpreterValue::Class { name, .. } => format!("class {}", name),
+			InterpreterValue::Enum(declaration) => {
+				format!("enum {}", declaration.name)
+			}
+			InterpreterValue::Variant {
+				enumeration,
+				index,
+				values,
+			} => {
+				let variant = &enumeration.variants[*index];
 
-				if key_num > take_amount {
-					obj_repr += &format!(
-						"{}...{} hidden{}}}",
-						if nested { "" } else { "\n\t" },
-						key_num - take_amount,
-						if nested { " " } else { ",\n" },
-					);
+				if variant.fields.is_some() {
+					format!(
+						"{}.{}({})",
+						enumeration.name,
+						variant.name,
+						values
+							.iter()
+							.map(|v| v.repr(true))
+							.collect::<Vec<String>>()
+							.join(", ")
+					)
 				} else {
-					obj_repr += "\n}";
+					format!("{}.{}", enumeration.name, variant.name)
 				}
-
-				obj_repr
 			}
-			InterpreterValue::Class { name, .. } => format!("class {}", name),
 			InterpreterValue::Function { .. } => String::from("function"),
 			InterpreterValue::String(s) => format!("{}", s),
 			InterpreterValue::Number(n) => format!("{}", n),
@@ -141,10 +177,49 @@ impl InterpreterValue {
 	}
 }
 
+fn properties_repr(
+	properties: &HashMap<String, InterpreterValue>,
+	nested: bool,
+) -> String {
+	let take_amount = if nested { 0 } else { MAX_LIST_VALUES_PRINT };
+
+	let mut obj_repr = String::from("{ ");
+
+	obj_repr += &properties
+		.iter()
+		.take(take_amount)
+		.map(|(k, v)| format!("\n\t{}: {},", k, v.repr(true)))
+		.collect::<String>();
+
+	let key_num = properties.len();
+
+	if key_num > take_amount {
+		obj_repr += &format!(
+			"{}...{} hidden{}}}",
+			if nested { "" } else { "\n\t" },
+			key_num - take_amount,
+			if nested { " " } else { ",\n" },
+		);
+	} else {
+		obj_repr += "\n}";
+	}
+
+	obj_repr
+}
+
 pub enum StmtResult<T> {
-	Return { keyword: Token, value: T },
-	Break(Token),
-	Continue(Token),
+	Return {
+		keyword: Token,
+		value: T,
+	},
+	Break {
+		keyword: Token,
+		label: Option<Token>,
+	},
+	Continue {
+		keyword: Token,
+		label: Option<Token>,
+	},
 	Noop,
 }
 
@@ -161,6 +236,51 @@ pub enum InterpreterFunction {
 		fun: NativeFunctionSignature,
 	},
 	LoxDefined(FunctionValue),
+	/// Constructs a value of the enum's variant with the given index
+	VariantConstructor {
+		enumeration: Rc<EnumDeclaration>,
+		index: usize,
+	},
+}
+
+/// Class members apart from the constructor and the instance methods
+pub struct ClassMembers {
+	pub static_methods: HashMap<String, InterpreterValue>,
+	pub static_fields: RefCell<HashMap<String, InterpreterValue>>,
+	pub getters: HashMap<String, InterpreterValue>,
+	pub setters: HashMap<String, InterpreterValue>,
+	/// instance fields get initialized in a fork of `env` on instantiation
+	pub fields: Rc<Vec<Field>>,
+	pub env: InterpreterEnvironment,
+}
+
+pub struct EnumDeclaration {
+	pub name: Rc<str>,
+	pub variants: Vec<EnumVariant>,
+}
+
+pub struct EnumVariant {
+	pub name: Rc<str>,
+	/// `None` for variants declared without parentheses
+	pub fields: Option<Vec<Rc<str>>>,
+}
+
+impl EnumDeclaration {
+	pub fn variant_index(&self, name: &str) -> Option<usize> {
+		self.variants.iter().position(|v| &*v.name == name)
+	}
+}
+
+impl PartialEq for EnumDeclaration {
+	fn eq(&self, other: &Self) -> bool {
+		std::ptr::eq(self, other)
+	}
+}
+
+impl PartialEq for ClassMembers {
+	fn eq(&self, other: &Self) -> bool {
+		std::ptr::eq(self, other)
+	}
 }
 
 impl PartialEq for InterpreterFunction {
@@ -176,6 +296,16 @@ impl PartialEq for InterpreterFunction {
 					..
 				}),
 			) => Rc::ptr_eq(body1, body2),
+			(
+				InterpreterFunction::VariantConstructor {
+					enumeration: enumeration1,
+					index: index1,
+				},
+				InterpreterFunction::VariantConstructor {
+					enumeration: enumeration2,
+					index: index2,
+				},
+			) => Rc::ptr_eq(enumeration1, enumeration2) && index1 == index2,
 			_ => false,
 		}
 	}
@@ -13,7 +13,11 @@ mod parser;
 mod resolver;
 mod runner;
 mod scanner;
+mod source;
 mod token;
 
 pub use runner::*;
 pub use runner::{run_file, run_repl};
+pub use scanner::{scan_lossless, ScanError};
+pub use source::{FileId, SourceFile, SourceMap};
+pub use token::{Location, TemplatePart, Token, TokenType, Trivia, TriviaKind};
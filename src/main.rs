@@ -4,7 +4,21 @@ use std::{env, process};
 fn main() {
 	let args: Vec<String> = env::args().collect();
 
-	if args.len() > 1 {
+	if args.len() > 2 && args[1] == "doc" {
+		for arg in args.iter().skip(2) {
+			match luxya::run_doc(arg) {
+				Err(luxya::RunError::Io(err)) => {
+					println!("{}", err);
+					process::exit(exitcode::IOERR);
+				}
+				Err(luxya::RunError::Exec) => {
+					println!("Errors while documenting {}", arg);
+					process::exit(exitcode::DATAERR);
+				}
+				_ => (),
+			}
+		}
+	} else if args.len() > 1 {
 		for arg in args.iter().skip(1) {
 			match luxya::run_file(arg) {
 				Err(luxya::RunError::Io(err)) => {
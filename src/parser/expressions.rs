@@ -1,6 +1,9 @@
 use super::{
+	helpers::starts_destructuring_assignment,
+	parse::declaration_or_value,
+	patterns::{declaration_pattern, pattern, pattern_bindings},
 	statements::block_statement,
-	types::{ParseError, ParserIter, Property},
+	types::{ParseError, ParserIter, Property, TokenStream},
 };
 use crate::{
 	ast::{
@@ -8,6 +11,8 @@ use crate::{
 			AssignmentValue,
 			BinaryValue,
 			CallValue,
+			ConditionalValue,
+			DestructuringAssignmentValue,
 			Expr,
 			FunctionValue,
 			GetAccessor,
@@ -15,10 +20,17 @@ use crate::{
 			GroupingValue,
 			IdentifierValue,
 			LiteralValue,
+			MatchArm,
+			MatchValue,
 			ObjectValue,
+			OptionalChainValue,
+			Parameter,
+			SequenceValue,
 			SetValue,
+			SpreadValue,
 			SuperAccessor,
 			SuperValue,
+			TemplateValue,
 			ThisValue,
 			UnaryValue,
 		},
@@ -29,7 +41,7 @@ use crate::{
 	expect_one,
 	match_then_consume,
 	peek_matches,
-	token::{Token, TokenType},
+	token::{TemplatePart, Token, TokenType},
 };
 
 use std::{cell::Cell, rc::Rc};
@@ -40,20 +52,40 @@ pub fn expression(tokens: ParserIter) -> Result<Expr, ParseError> {
 }
 
 fn assignment(tokens: ParserIter) -> Result<Expr, ParseError> {
-	let expr = logic_or(tokens)?;
+	if starts_destructuring_assignment(tokens) {
+		return destructuring_assignment(tokens);
+	}
+
+	let expr = nil_coalescing(tokens)?;
+
+	if let Some(equals) = match_then_consume!(
+		tokens,
+		TokenType::Equal
+			| TokenType::PlusEqual
+			| TokenType::MinusEqual
+			| TokenType::StarEqual
+			| TokenType::SlashEqual
+			| TokenType::ModuloEqual
+	) {
+		let operator = compound_operator(&equals);
 
-	if let Some(equals) = match_then_consume!(tokens, TokenType::Equal) {
 		match expr {
 			Expr::Identifier(v) => Ok(Expr::Assignment(AssignmentValue {
 				name: v.name,
 				value: Box::new(assignment(tokens)?),
 				env_distance: Cell::new(0),
+				operator,
 			})),
+			Expr::OptionalChain(_) => Err(ParseError {
+				token: Some(equals),
+				message: "Cannot assign to an optional chain".into(),
+			}),
 			Expr::Get(v) => Ok(Expr::Set(SetValue {
 				setee: v.getee,
 				key: v.key,
 				blame: v.blame,
 				value: Box::new(assignment(tokens)?),
+				operator,
 			})),
 			_ => Err(ParseError {
 				token: Some(equals),
@@ -68,6 +100,60 @@ fn assignment(tokens: ParserIter) -> Result<Expr, ParseError> {
 	}
 }
 
+/// Parses an assignment with a list or object pattern in place of the name
+fn destructuring_assignment(tokens: ParserIter) -> Result<Expr, ParseError> {
+	// unwrap_unchecked because we just peeked the pattern 😇
+	let blame = unsafe { tokens.peek().cloned().unwrap_unchecked() };
+
+	let pattern = declaration_pattern(tokens)?;
+
+	expect_one!(tokens, TokenType::Equal)?;
+
+	let mut bindings = Vec::new();
+	pattern_bindings(&pattern, &mut bindings);
+
+	let targets = bindings
+		.into_iter()
+		.map(|name| {
+			Expr::Identifier(IdentifierValue {
+				name: name.clone(),
+				env_distance: Cell::new(0),
+			})
+		})
+		.collect();
+
+	Ok(Expr::DestructuringAssignment(
+		DestructuringAssignmentValue {
+			pattern,
+			value: Box::new(assignment(tokens)?),
+			targets,
+			blame,
+		},
+	))
+}
+
+/// Turns a compound assignment operator (e.g. `+=`) into the binary operator
+/// it applies, blaming the original token
+fn compound_operator(token: &Token) -> Option<Token> {
+	let token_type = match token.token_type {
+		TokenType::PlusEqual => TokenType::Plus,
+		TokenType::MinusEqual => TokenType::Minus,
+		TokenType::StarEqual => TokenType::Star,
+		TokenType::SlashEqual => TokenType::Slash,
+		TokenType::ModuloEqual => TokenType::Modulo,
+		_ => return None,
+	};
+
+	Some(Token {
+		token_type,
+		..token.clone()
+	})
+}
+
+fn nil_coalescing(tokens: ParserIter) -> Result<Expr, ParseError> {
+	build_binary_expr!(tokens, logic_or, TokenType::QuestionQuestion)
+}
+
 fn logic_or(tokens: ParserIter) -> Result<Expr, ParseError> {
 	build_binary_expr!(tokens, logic_and, TokenType::Or)
 }
@@ -87,7 +173,7 @@ fn equality(tokens: ParserIter) -> Result<Expr, ParseError> {
 fn comparison(tokens: ParserIter) -> Result<Expr, ParseError> {
 	build_binary_expr!(
 		tokens,
-		term,
+		range,
 		TokenType::Greater
 			| TokenType::GreaterEqual
 			| TokenType::Less
@@ -95,6 +181,36 @@ fn comparison(tokens: ParserIter) -> Result<Expr, ParseError> {
 	)
 }
 
+fn range(tokens: ParserIter) -> Result<Expr, ParseError> {
+	build_binary_expr!(
+		tokens,
+		bitwise_or,
+		TokenType::DotDot | TokenType::DotDotEqual
+	)
+}
+
+// bitwise operators bind tighter than comparisons, so that `a & 1 == 0` means
+// `(a & 1) == 0`
+fn bitwise_or(tokens: ParserIter) -> Result<Expr, ParseError> {
+	build_binary_expr!(tokens, bitwise_xor, TokenType::Pipe)
+}
+
+fn bitwise_xor(tokens: ParserIter) -> Result<Expr, ParseError> {
+	build_binary_expr!(tokens, bitwise_and, TokenType::Caret)
+}
+
+fn bitwise_and(tokens: ParserIter) -> Result<Expr, ParseError> {
+	build_binary_expr!(tokens, shift, TokenType::Ampersand)
+}
+
+fn shift(tokens: ParserIter) -> Result<Expr, ParseError> {
+	build_binary_expr!(
+		tokens,
+		term,
+		TokenType::LessLess | TokenType::GreaterGreater,
+	)
+}
+
 fn term(tokens: ParserIter) -> Result<Expr, ParseError> {
 	build_binary_expr!(
 		tokens,
@@ -104,13 +220,17 @@ fn term(tokens: ParserIter) -> Result<Expr, ParseError> {
 }
 
 fn factor(tokens: ParserIter) -> Result<Expr, ParseError> {
-	build_binary_expr!(tokens, unary, TokenType::Slash | TokenType::Star)
+	build_binary_expr!(
+		tokens,
+		unary,
+		TokenType::Slash | TokenType::Star | TokenType::TildeSlash,
+	)
 }
 
 fn unary(tokens: ParserIter) -> Result<Expr, ParseError> {
 	if matches!(
 		tokens.peek().map(|t| &t.token_type),
-		Some(TokenType::Bang | TokenType::Minus)
+		Some(TokenType::Bang | TokenType::Minus | TokenType::Tilde)
 	) {
 		let operator = tokens.next().unwrap();
 
@@ -121,83 +241,160 @@ fn unary(tokens: ParserIter) -> Result<Expr, ParseError> {
 			right: Box::new(right),
 		}))
 	} else {
-		function_declaration(tokens, false)
+		exponent(tokens)
 	}
 }
 
-pub fn function_declaration(
+/// `**` binds tighter than unary operators on its left (`-2 ** 2` is `-4`),
+/// and is right-associative (`2 ** 3 ** 2` is `2 ** 9`)
+fn exponent(tokens: ParserIter) -> Result<Expr, ParseError> {
+	let base = function_declaration(tokens)?;
+
+	if let Some(operator) = match_then_consume!(tokens, TokenType::StarStar) {
+		Ok(Expr::Binary(BinaryValue {
+			left: Box::new(base),
+			operator,
+			right: Box::new(unary(tokens)?),
+		}))
+	} else {
+		Ok(base)
+	}
+}
+
+pub fn function_declaration(tokens: ParserIter) -> Result<Expr, ParseError> {
+	if let Some(keyword) = match_then_consume!(tokens, TokenType::Fun) {
+		let name = match_then_consume!(tokens, TokenType::Identifier(_));
+		let doc = keyword.doc_comment();
+
+		finish_function(tokens, keyword, name, doc)
+	} else {
+		call(tokens)
+	}
+}
+
+/// Parses a method, whose name (along with modifiers like `static`) has
+/// already been consumed
+pub fn method_declaration(
 	tokens: ParserIter,
-	method: bool,
+	name: Token,
+	doc: Option<Rc<str>>,
 ) -> Result<Expr, ParseError> {
-	// methods don't have the `fun` keyword
-	if method || peek_matches!(tokens, TokenType::Fun) {
-		// expect the `fun` keyword if normal function, an identifier otherwise
-		let keyword = if method {
-			expect!(tokens, TokenType::Identifier(_), "Expected method name",)?
-		} else {
-			expect_one!(tokens, TokenType::Fun)?
-		};
-
-		// if we're parsing a method, we actually already have
-		// parse it's name in the keyword
-		let name = if method {
-			Some(keyword.clone())
-		} else {
-			match_then_consume!(tokens, TokenType::Identifier(_))
-		};
+	// methods don't have the `fun` keyword, so their name is the keyword
+	finish_function(tokens, name.clone(), Some(name), doc)
+}
 
-		// intro to parameter parsing
-		expect_one!(tokens, TokenType::LeftParen)?;
+fn finish_function(
+	tokens: ParserIter,
+	keyword: Token,
+	name: Option<Token>,
+	doc: Option<Rc<str>>,
+) -> Result<Expr, ParseError> {
+	// intro to parameter parsing
+	expect_one!(tokens, TokenType::LeftParen)?;
 
-		let mut params = Vec::new();
+	let mut params = Vec::new();
 
-		// parse parameters
-		while !peek_matches!(tokens, TokenType::RightParen) {
-			params.push(expect!(
-				tokens,
-				TokenType::Identifier(_),
-				"Expected parameter name"
-			)?);
+	// parse parameters
+	while !peek_matches!(tokens, TokenType::RightParen) {
+		params.push(parameter(tokens, &params)?);
 
-			if match_then_consume!(tokens, TokenType::Comma).is_none() {
-				break;
-			}
+		if match_then_consume!(tokens, TokenType::Comma).is_none() {
+			break;
 		}
+	}
 
-		expect_one!(tokens, TokenType::RightParen)?;
-		// outro of parameter parsing
+	expect_one!(tokens, TokenType::RightParen)?;
+	// outro of parameter parsing
 
-		// parse the body
-		expect_one!(tokens, TokenType::LeftBrace)?;
+	// parse the body
+	expect_one!(tokens, TokenType::LeftBrace)?;
 
-		let body = block_statement(tokens)?;
+	let body = block_statement(tokens)?;
 
-		let statements = if let Some(Stmt::Block(bv)) = body {
-			Some(Rc::new(bv.statements))
+	let statements = if let Some(Stmt::Block(bv)) = body {
+		Some(Rc::new(bv.statements))
+	} else {
+		None
+	};
+
+	Ok(Expr::Function(FunctionValue {
+		body: statements,
+		doc,
+		keyword,
+		name,
+		params: if params.is_empty() {
+			None
+		} else {
+			Some(Rc::new(params))
+		},
+	}))
+}
+
+/// Parses a single parameter, along with its default value or the rest
+/// marker, making sure it can follow the already parsed ones
+fn parameter(
+	tokens: ParserIter,
+	previous: &[Parameter],
+) -> Result<Parameter, ParseError> {
+	let rest = match_then_consume!(tokens, TokenType::DotDotDot).is_some();
+
+	let name =
+		expect!(tokens, TokenType::Identifier(_), "Expected parameter name")?;
+
+	if previous.last().is_some_and(|p| p.rest) {
+		return Err(ParseError {
+			token: Some(name),
+			message: "Rest parameter has to be the last one".into(),
+		});
+	}
+
+	let default =
+		if !rest && match_then_consume!(tokens, TokenType::Equal).is_some() {
+			Some(expression(tokens)?)
 		} else {
 			None
 		};
 
-		Ok(Expr::Function(FunctionValue {
-			body: statements,
-			keyword,
-			name,
-			params: if params.is_empty() {
-				None
-			} else {
-				Some(Rc::new(params))
-			},
+	if default.is_none()
+		&& !rest
+		&& previous.last().is_some_and(|p| p.default.is_some())
+	{
+		return Err(ParseError {
+			token: Some(name),
+			message: "Parameters without a default value can't follow the \
+			          ones with it"
+				.into(),
+		});
+	}
+
+	Ok(Parameter {
+		name,
+		default,
+		rest,
+	})
+}
+
+/// Parses an expression, that can be spread with `...`, e.g. a call argument
+fn spreadable(tokens: ParserIter) -> Result<Expr, ParseError> {
+	if let Some(blame) = match_then_consume!(tokens, TokenType::DotDotDot) {
+		Ok(Expr::Spread(SpreadValue {
+			blame,
+			value: Box::new(expression(tokens)?),
 		}))
 	} else {
-		call(tokens)
+		expression(tokens)
 	}
 }
 
-fn finish_call(tokens: ParserIter, calee: Expr) -> Result<Expr, ParseError> {
+fn finish_call(
+	tokens: ParserIter,
+	calee: Expr,
+	optional: bool,
+) -> Result<Expr, ParseError> {
 	let mut arguments = Vec::new();
 
 	while !peek_matches!(tokens, TokenType::RightParen) {
-		arguments.push(expression(tokens)?);
+		arguments.push(spreadable(tokens)?);
 
 		if match_then_consume!(tokens, TokenType::Comma).is_none() {
 			break;
@@ -208,10 +405,15 @@ fn finish_call(tokens: ParserIter, calee: Expr) -> Result<Expr, ParseError> {
 		arguments,
 		calee: Box::new(calee),
 		closing_paren: expect_one!(tokens, TokenType::RightParen)?,
+		optional,
 	}))
 }
 
-fn finish_get(tokens: ParserIter, getee: Expr) -> Result<Expr, ParseError> {
+fn finish_get(
+	tokens: ParserIter,
+	getee: Expr,
+	optional: bool,
+) -> Result<Expr, ParseError> {
 	let peek = tokens.peek();
 	let peek_token_type = peek.as_ref().map(|c| c.token_type.clone());
 
@@ -226,12 +428,19 @@ fn finish_get(tokens: ParserIter, getee: Expr) -> Result<Expr, ParseError> {
 				getee: Box::new(getee),
 				key: GetAccessor::DotName(i),
 				blame,
+				optional,
 			}))
 		}
 		Some(TokenType::LeftParen) => {
 			// same here, unwrapping what we already matched
 			let blame = unsafe { tokens.next().unwrap_unchecked() };
 
+			// `?.()` is an optional call, as an empty grouping accessor
+			// wouldn't make any sense
+			if optional && peek_matches!(tokens, TokenType::RightParen) {
+				return finish_call(tokens, getee, true);
+			}
+
 			let eval = expression(tokens)?;
 
 			expect_one!(tokens, TokenType::RightParen)?;
@@ -240,6 +449,7 @@ fn finish_get(tokens: ParserIter, getee: Expr) -> Result<Expr, ParseError> {
 				getee: Box::new(getee),
 				key: GetAccessor::DotEval(Box::new(eval)),
 				blame,
+				optional,
 			}))
 		}
 		_ => Err(ParseError {
@@ -251,61 +461,107 @@ fn finish_get(tokens: ParserIter, getee: Expr) -> Result<Expr, ParseError> {
 	}
 }
 
-fn finish_sub(tokens: ParserIter, getee: Expr) -> Result<Expr, ParseError> {
-	let peek = tokens.peek();
-	let peek_type = peek.as_ref().map(|p| p.token_type.clone());
-
-	let accessor = if let Some(TokenType::Number(n)) = peek_type {
-		// unwrap_unchecked because we just matched peek 😇
-		//
-		// Consuming the next token here, because I want to advance the
-		// iterator
-		let blame = unsafe { tokens.next().unwrap_unchecked() };
-
-		Expr::Get(GetValue {
-			getee: Box::new(getee),
-			key: GetAccessor::SubscriptionNumber(n),
-			blame,
-		})
-	} else {
-		// unwrapping what we already matched
-		let blame = unsafe { peek.unwrap_unchecked().clone() };
+fn finish_sub(
+	tokens: ParserIter,
+	getee: Expr,
+	optional: bool,
+) -> Result<Expr, ParseError> {
+	let blame = tokens.peek().cloned().ok_or_else(|| ParseError {
+		token: None,
+		message: "Unexpected EOF".into(),
+	})?;
 
-		let eval = expression(tokens)?;
+	let index = slice_bound(tokens)?;
 
-		Expr::Get(GetValue {
-			getee: Box::new(getee),
-			key: GetAccessor::SubscriptionEval(Box::new(eval)),
-			blame,
-		})
-	};
+	let key = if match_then_consume!(tokens, TokenType::Colon).is_some() {
+		let end = slice_bound(tokens)?;
 
+		let step = if match_then_consume!(tokens, TokenType::Colon).is_some() {
+			slice_bound(tokens)?
+		} else {
+			None
+		};
+
+		GetAccessor::Slice {
+			start: index,
+			end,
+			step,
+		}
+	} else if let Some(index) = index {
+		// number literals are the most common indices, so they skip the
+		// evaluation
+		if let Expr::Literal(LiteralValue::Number(n)) = *index {
+			GetAccessor::SubscriptionNumber(n)
+		} else {
+			GetAccessor::SubscriptionEval(index)
+		}
+	} else {
+		return Err(ParseError {
+			token: Some(blame),
+			message: "Expected an index or a slice".into(),
+		});
+	};
 
 	expect_one!(tokens, TokenType::RightSquareBracket)?;
 
-	Ok(accessor)
+	Ok(Expr::Get(GetValue {
+		getee: Box::new(getee),
+		key,
+		blame,
+		optional,
+	}))
+}
+
+/// Parses a part of a subscription, which is left out when followed by `:`
+/// or `]`
+fn slice_bound(tokens: ParserIter) -> Result<Option<Box<Expr>>, ParseError> {
+	if peek_matches!(tokens, TokenType::Colon | TokenType::RightSquareBracket) {
+		Ok(None)
+	} else {
+		Ok(Some(Box::new(expression(tokens)?)))
+	}
 }
 
 fn call(tokens: ParserIter) -> Result<Expr, ParseError> {
 	let mut expr = primary(tokens)?;
+	let mut optional = false;
 
 	while let Some(consumed) = match_then_consume!(
 		tokens,
-		TokenType::LeftParen | TokenType::Dot | TokenType::LeftSquareBracket
+		TokenType::LeftParen
+			| TokenType::Dot
+			| TokenType::LeftSquareBracket
+			| TokenType::QuestionDot
+			| TokenType::QuestionLeftSquareBracket
 	) {
 		match consumed.token_type {
 			TokenType::LeftParen => {
-				expr = finish_call(tokens, expr)?;
+				expr = finish_call(tokens, expr, false)?;
 			}
 			TokenType::LeftSquareBracket => {
-				expr = finish_sub(tokens, expr)?;
+				expr = finish_sub(tokens, expr, false)?;
+			}
+			TokenType::QuestionLeftSquareBracket => {
+				expr = finish_sub(tokens, expr, true)?;
+				optional = true;
+			}
+			TokenType::QuestionDot => {
+				expr = finish_get(tokens, expr, true)?;
+				optional = true;
 			}
 			_ => {
-				expr = finish_get(tokens, expr)?;
+				expr = finish_get(tokens, expr, false)?;
 			}
 		}
 	}
 
+	// the whole chain gets skipped once any of its optional links hits `nil`
+	if optional {
+		expr = Expr::OptionalChain(OptionalChainValue {
+			expression: Box::new(expr),
+		});
+	}
+
 	Ok(expr)
 }
 
@@ -326,6 +582,7 @@ fn primary(tokens: ParserIter) -> Result<Expr, ParseError> {
 		TokenType::String(s) => Ok(Expr::Literal(LiteralValue::String(s))),
 		TokenType::Char(c) => Ok(Expr::Literal(LiteralValue::Char(c))),
 		TokenType::Number(n) => Ok(Expr::Literal(LiteralValue::Number(n))),
+		TokenType::Template(parts) => parse_template(&parts),
 
 		TokenType::Identifier(_) => Ok(Expr::Identifier(IdentifierValue {
 			name: token,
@@ -358,6 +615,12 @@ fn primary(tokens: ParserIter) -> Result<Expr, ParseError> {
 		// Super
 		TokenType::Super => parse_super(tokens, token),
 
+		// If expression
+		TokenType::If => parse_conditional(tokens),
+
+		// Match
+		TokenType::Match => parse_match(tokens, token),
+
 		_ => Err(ParseError {
 			token: Some(token),
 			message: "Expected expression".into(),
@@ -367,6 +630,109 @@ fn primary(tokens: ParserIter) -> Result<Expr, ParseError> {
 
 // Singular-primary parsing functions down there 👇
 
+pub fn parse_conditional(tokens: ParserIter) -> Result<Expr, ParseError> {
+	let condition = expression(tokens)?;
+
+	expect_one!(tokens, TokenType::LeftBrace)?;
+
+	let then = parse_sequence(tokens)?;
+
+	let otherwise = if match_then_consume!(tokens, TokenType::Else).is_some() {
+		let branch = expect!(
+			tokens,
+			TokenType::LeftBrace | TokenType::If,
+			"Expected a block or `if` after `else`"
+		)?;
+
+		Some(Box::new(if branch.token_type == TokenType::If {
+			parse_conditional(tokens)?
+		} else {
+			parse_sequence(tokens)?
+		}))
+	} else {
+		None
+	};
+
+	Ok(Expr::Conditional(ConditionalValue {
+		condition: Box::new(condition),
+		then: Box::new(then),
+		otherwise,
+	}))
+}
+
+pub fn parse_match(
+	tokens: ParserIter,
+	keyword: Token,
+) -> Result<Expr, ParseError> {
+	let value = expression(tokens)?;
+
+	expect_one!(tokens, TokenType::LeftBrace)?;
+
+	let mut arms = Vec::new();
+
+	while !peek_matches!(tokens, TokenType::RightBrace) {
+		let pattern = pattern(tokens)?;
+
+		let guard = if match_then_consume!(tokens, TokenType::If).is_some() {
+			Some(expression(tokens)?)
+		} else {
+			None
+		};
+
+		expect_one!(tokens, TokenType::FatArrow)?;
+
+		// a brace after the arrow starts a block, rather than an object
+		let body =
+			if match_then_consume!(tokens, TokenType::LeftBrace).is_some() {
+				parse_sequence(tokens)?
+			} else {
+				expression(tokens)?
+			};
+
+		let block_body = matches!(body, Expr::Sequence(_));
+
+		arms.push(MatchArm {
+			pattern,
+			guard,
+			body,
+		});
+
+		// blocks don't need to be followed by a comma
+		if match_then_consume!(tokens, TokenType::Comma).is_none()
+			&& !block_body
+		{
+			break;
+		}
+	}
+
+	expect_one!(tokens, TokenType::RightBrace)?;
+
+	Ok(Expr::Match(MatchValue {
+		keyword,
+		value: Box::new(value),
+		arms,
+	}))
+}
+
+/// Parses a block yielding the value of the trailing expression - the one
+/// not followed by a semicolon
+///
+/// expects the `{` to be already consumed
+fn parse_sequence(tokens: ParserIter) -> Result<Expr, ParseError> {
+	let mut statements = Vec::new();
+	let mut value = None;
+
+	while value.is_none() && !peek_matches!(tokens, TokenType::RightBrace) {
+		if let Some(stmt) = declaration_or_value(tokens, Some(&mut value))? {
+			statements.push(stmt);
+		}
+	}
+
+	expect_one!(tokens, TokenType::RightBrace)?;
+
+	Ok(Expr::Sequence(SequenceValue { statements, value }))
+}
+
 pub fn parse_object(
 	tokens: ParserIter,
 	blame: Token,
@@ -423,7 +789,7 @@ pub fn parse_super(
 
 	let accessor = match tokens.next().map(|next| next.token_type) {
 		Some(TokenType::LeftParen) => {
-			let call_expr = finish_call(tokens, dummy_expr)?;
+			let call_expr = finish_call(tokens, dummy_expr, false)?;
 
 			let arguments = if let Expr::Call(cv) = call_expr {
 				cv.arguments
@@ -459,11 +825,34 @@ pub fn parse_super(
 	}))
 }
 
+pub fn parse_template(parts: &[TemplatePart]) -> Result<Expr, ParseError> {
+	let parts = parts
+		.iter()
+		.map(|part| match part {
+			TemplatePart::Literal(s) => {
+				Ok(Expr::Literal(LiteralValue::String(Rc::clone(s))))
+			}
+			TemplatePart::Expression(part_tokens) => {
+				let tokens: ParserIter =
+					&mut TokenStream::new(part_tokens.clone());
+
+				let expr = expression(tokens)?;
+
+				expect_one!(tokens, TokenType::RightBrace)?;
+
+				Ok(expr)
+			}
+		})
+		.collect::<Result<Vec<Expr>, ParseError>>()?;
+
+	Ok(Expr::Template(TemplateValue { parts }))
+}
+
 pub fn parse_list(tokens: ParserIter) -> Result<Expr, ParseError> {
 	let mut values = Vec::new();
 
 	while !peek_matches!(tokens, TokenType::RightSquareBracket) {
-		values.push(expression(tokens)?);
+		values.push(spreadable(tokens)?);
 
 		if match_then_consume!(tokens, TokenType::Comma).is_none() {
 			break;
@@ -1,4 +1,4 @@
-use super::types::{ParseError, ParserIter};
+use super::types::{ParseError, ParserIter, TokenStream};
 use crate::{
 	expect_one,
 	token::{Token, TokenType},
@@ -16,13 +16,18 @@ pub fn synchronize(tokens: ParserIter) {
 	while let Some(token) = tokens.peek() {
 		match token.token_type {
 			TokenType::Class
+			| TokenType::Enum
 			| TokenType::Fun
 			| TokenType::Let
 			| TokenType::Const
 			| TokenType::For
 			| TokenType::If
 			| TokenType::Print
-			| TokenType::Return => {
+			| TokenType::Return
+			| TokenType::Throw
+			| TokenType::Try
+			| TokenType::Import
+			| TokenType::Export => {
 				break;
 			}
 
@@ -35,6 +40,45 @@ pub fn synchronize(tokens: ParserIter) {
 	}
 }
 
+/// Checks whether the tokens start with a list or an object pattern followed
+/// by `=`, which can't be told apart from a literal or a block up front
+pub fn starts_destructuring_assignment(tokens: &TokenStream) -> bool {
+	let ahead = tokens.ahead();
+
+	if !matches!(
+		ahead.first().map(|t| &t.token_type),
+		Some(TokenType::LeftSquareBracket | TokenType::LeftBrace)
+	) {
+		return false;
+	}
+
+	let mut depth = 0_usize;
+
+	for (i, token) in ahead.iter().enumerate() {
+		match token.token_type {
+			TokenType::LeftSquareBracket
+			| TokenType::QuestionLeftSquareBracket
+			| TokenType::LeftBrace
+			| TokenType::LeftParen => depth += 1,
+			TokenType::RightSquareBracket
+			| TokenType::RightBrace
+			| TokenType::RightParen => {
+				depth -= 1;
+
+				if depth == 0 {
+					return matches!(
+						ahead.get(i + 1).map(|t| &t.token_type),
+						Some(TokenType::Equal)
+					);
+				}
+			}
+			_ => (),
+		}
+	}
+
+	false
+}
+
 #[macro_export]
 macro_rules! peek_matches {
 	($tokens:expr, $( $expected:pat )|+ $(,)?) => {
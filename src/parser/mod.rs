@@ -1,8 +1,10 @@
 mod expressions;
 mod helpers;
 mod parse;
+mod patterns;
 mod statements;
 
 pub mod types;
 
 pub use parse::parse;
+pub use patterns::pattern_bindings;
@@ -1,29 +1,50 @@
 use super::{
 	expressions::expression,
-	helpers::{expect_semicolon, synchronize},
+	helpers::{expect_semicolon, starts_destructuring_assignment, synchronize},
+	patterns::declaration_pattern,
 	statements::{
 		block_statement,
 		break_statement,
 		class_statement,
 		continue_statement,
+		enum_statement,
+		export_statement,
 		expression_statement,
 		for_statement,
 		if_statement,
+		import_statement,
 		print_statement,
 		return_statement,
+		throw_statement,
+		try_statement,
 	},
-	types::{ParseError, ParserIter},
+	types::{ParseError, ParserIter, TokenStream},
 };
 use crate::{
-	ast::stmt::{DeclarationValue, Stmt},
+	ast::{
+		expr::Expr,
+		stmt::{DeclarationValue, DestructureValue, Stmt},
+	},
 	expect,
 	match_then_consume,
-	token::{Token, TokenType},
+	peek_matches,
+	token::{Location, Token, TokenType},
 };
 
 
 pub fn parse(tokens: Vec<Token>) -> (Vec<Stmt>, Vec<ParseError>) {
-	let tokens: ParserIter = &mut tokens.into_iter().peekable();
+	// errors hitting the end of input get blamed right after the last token
+	let eof = tokens.last().map(|last| Token {
+		location: Location {
+			file: last.location.file,
+			byte_offset: last.location.byte_offset + last.location.byte_length,
+			byte_length: 1,
+		},
+		token_type: last.token_type.clone(),
+		leading_trivia: Vec::new(),
+	});
+
+	let tokens: ParserIter = &mut TokenStream::new(tokens);
 
 	let mut statements = Vec::new();
 	let mut errors = Vec::new();
@@ -35,7 +56,10 @@ pub fn parse(tokens: Vec<Token>) -> (Vec<Stmt>, Vec<ParseError>) {
 			Err(s) => {
 				synchronize(tokens);
 
-				errors.push(s);
+				errors.push(ParseError {
+					token: s.token.or_else(|| eof.clone()),
+					..s
+				});
 			}
 			_ => (),
 		}
@@ -45,9 +69,26 @@ pub fn parse(tokens: Vec<Token>) -> (Vec<Stmt>, Vec<ParseError>) {
 }
 
 pub fn declaration(tokens: ParserIter) -> Result<Option<Stmt>, ParseError> {
+	declaration_or_value(tokens, None)
+}
+
+/// Same as `declaration`, but when `value` is passed, an expression left
+/// without a semicolon right before the closing `}` is stored there as the
+/// value of the enclosing sequence
+pub fn declaration_or_value(
+	tokens: ParserIter,
+	value: Option<&mut Option<Box<Expr>>>,
+) -> Result<Option<Stmt>, ParseError> {
 	if let Some(token) =
 		match_then_consume!(tokens, TokenType::Let | TokenType::Const)
 	{
+		if peek_matches!(
+			tokens,
+			TokenType::LeftSquareBracket | TokenType::LeftBrace
+		) {
+			return destructuring_declaration(tokens, &token);
+		}
+
 		let name =
 			expect!(tokens, TokenType::Identifier(_), "Expected identifier",)?;
 
@@ -64,15 +105,63 @@ pub fn declaration(tokens: ParserIter) -> Result<Option<Stmt>, ParseError> {
 			name,
 			initializer,
 			mutable: TokenType::Let == token.token_type,
+			doc: token.doc_comment(),
 		})))
 	} else {
-		statement(tokens)
+		statement_or_value(tokens, value)
 	}
 }
 
+/// Parses a `let`/`const` declaration with a list or object pattern in place
+/// of the name
+fn destructuring_declaration(
+	tokens: ParserIter,
+	keyword: &Token,
+) -> Result<Option<Stmt>, ParseError> {
+	// unwrap_unchecked because we just peeked the pattern 😇
+	let blame = unsafe { tokens.peek().cloned().unwrap_unchecked() };
+
+	let pattern = declaration_pattern(tokens)?;
+
+	expect!(
+		tokens,
+		TokenType::Equal,
+		"Expected `=`, destructuring declarations need an initializer",
+	)?;
+
+	let initializer = expression(tokens)?;
+
+	expect_semicolon(tokens)?;
+
+	Ok(Some(Stmt::Destructure(DestructureValue {
+		pattern,
+		initializer,
+		mutable: TokenType::Let == keyword.token_type,
+		blame,
+		doc: keyword.doc_comment(),
+	})))
+}
+
 /// Statement can not fail and produce None for a statement, because it wouldn't
 /// be significant (e.g. lone `;`)
 pub fn statement(tokens: ParserIter) -> Result<Option<Stmt>, ParseError> {
+	statement_or_value(tokens, None)
+}
+
+fn statement_or_value(
+	tokens: ParserIter,
+	value: Option<&mut Option<Box<Expr>>>,
+) -> Result<Option<Stmt>, ParseError> {
+	// inside sequences `if` is an expression, so that it can yield the value
+	if value.is_some() && peek_matches!(tokens, TokenType::If) {
+		return expression_statement(tokens, value);
+	}
+
+	// `{ a, b } = c;` would be taken for a block otherwise
+	if starts_destructuring_assignment(tokens) {
+		return expression_statement(tokens, value);
+	}
+
 	let consumed_token = match_then_consume!(
 		tokens,
 		TokenType::If
@@ -80,8 +169,13 @@ pub fn statement(tokens: ParserIter) -> Result<Option<Stmt>, ParseError> {
 			| TokenType::Print
 			| TokenType::Break
 			| TokenType::Class
+			| TokenType::Enum
 			| TokenType::Return
 			| TokenType::Continue
+			| TokenType::Throw
+			| TokenType::Try
+			| TokenType::Import
+			| TokenType::Export
 			| TokenType::LeftBrace
 			| TokenType::Semicolon
 	);
@@ -90,9 +184,14 @@ pub fn statement(tokens: ParserIter) -> Result<Option<Stmt>, ParseError> {
 
 	match token_type {
 		Some(TokenType::If) => if_statement(tokens),
-		Some(TokenType::For) => for_statement(tokens),
+		Some(TokenType::For) => for_statement(tokens, None),
 		Some(TokenType::Print) => print_statement(tokens),
-		Some(TokenType::Class) => class_statement(tokens),
+		Some(TokenType::Class) => unsafe {
+			class_statement(tokens, &consumed_token.unwrap_unchecked())
+		},
+		Some(TokenType::Enum) => unsafe {
+			enum_statement(tokens, &consumed_token.unwrap_unchecked())
+		},
 		Some(TokenType::LeftBrace) => block_statement(tokens),
 		Some(TokenType::Break) => unsafe {
 			break_statement(tokens, consumed_token.unwrap_unchecked())
@@ -103,10 +202,18 @@ pub fn statement(tokens: ParserIter) -> Result<Option<Stmt>, ParseError> {
 		Some(TokenType::Continue) => unsafe {
 			continue_statement(tokens, consumed_token.unwrap_unchecked())
 		},
+		Some(TokenType::Throw) => unsafe {
+			throw_statement(tokens, consumed_token.unwrap_unchecked())
+		},
+		Some(TokenType::Try) => try_statement(tokens),
+		Some(TokenType::Import) => import_statement(tokens),
+		Some(TokenType::Export) => unsafe {
+			export_statement(tokens, consumed_token.unwrap_unchecked())
+		},
 
 		// We allow trails of semicolons and treat them as empty statements
 		Some(TokenType::Semicolon) => Ok(None),
 
-		_ => expression_statement(tokens),
+		_ => expression_statement(tokens, value),
 	}
 }
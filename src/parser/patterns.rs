@@ -0,0 +1,343 @@
+use super::types::{ParseError, ParserIter};
+use crate::{
+	ast::expr::{
+		Expr,
+		GetAccessor,
+		GetValue,
+		IdentifierValue,
+		LiteralValue,
+		Pattern,
+		PatternType,
+		PropertyPattern,
+	},
+	expect,
+	expect_one,
+	match_then_consume,
+	peek_matches,
+	token::{Token, TokenType},
+};
+
+use std::cell::Cell;
+
+
+/// Type names usable in type patterns, any other name refers to a class
+const BUILTIN_TYPES: [&str; 10] = [
+	"number", "string", "char", "boolean", "list", "range", "function",
+	"class", "object", "variant",
+];
+
+pub fn pattern(tokens: ParserIter) -> Result<Pattern, ParseError> {
+	parse_pattern(tokens, false)
+}
+
+/// Parses a pattern of a destructuring declaration or assignment, which can
+/// only consist of bindings, list and object patterns
+pub fn declaration_pattern(tokens: ParserIter) -> Result<Pattern, ParseError> {
+	parse_pattern(tokens, true)
+}
+
+fn parse_pattern(
+	tokens: ParserIter,
+	in_declaration: bool,
+) -> Result<Pattern, ParseError> {
+	let token = if let Some(token) = tokens.next() {
+		token
+	} else {
+		return Err(ParseError {
+			token: None,
+			message: "Unexpected EOF".into(),
+		});
+	};
+
+	if in_declaration
+		&& matches!(
+			token.token_type,
+			TokenType::False
+				| TokenType::True
+				| TokenType::Nil
+				| TokenType::String(_)
+				| TokenType::Char(_)
+				| TokenType::Number(_)
+				| TokenType::Minus
+		) {
+		return Err(ParseError {
+			token: Some(token),
+			message: "Cannot use literal patterns in destructuring".into(),
+		});
+	}
+
+	match &token.token_type {
+		TokenType::False => Ok(literal(LiteralValue::False)),
+		TokenType::True => Ok(literal(LiteralValue::True)),
+		TokenType::Nil => Ok(literal(LiteralValue::Nil)),
+		TokenType::String(s) => Ok(literal(LiteralValue::String(s.clone()))),
+		TokenType::Char(c) => Ok(literal(LiteralValue::Char(*c))),
+		TokenType::Number(n) => Ok(literal(LiteralValue::Number(*n))),
+		TokenType::Minus => {
+			let number = expect!(
+				tokens,
+				TokenType::Number(_),
+				"Expected a number after `-` in pattern",
+			)?;
+
+			if let TokenType::Number(n) = number.token_type {
+				Ok(literal(LiteralValue::Number(-n)))
+			} else {
+				unreachable!("We just matched a number")
+			}
+		}
+		TokenType::LeftSquareBracket => list_pattern(tokens, in_declaration),
+		TokenType::LeftBrace => object_pattern(tokens, None, in_declaration),
+		TokenType::Identifier(name) => {
+			let name = name.clone();
+
+			identifier_pattern(tokens, &name, token, in_declaration)
+		}
+		_ => Err(ParseError {
+			token: Some(token),
+			message: "Expected pattern".into(),
+		}),
+	}
+}
+
+/// Parses a pattern starting with a name - a binding, a type pattern or an
+/// object pattern with a class
+fn identifier_pattern(
+	tokens: ParserIter,
+	name: &str,
+	token: Token,
+	in_declaration: bool,
+) -> Result<Pattern, ParseError> {
+	// `Shape.Circle` refers to a member of a class or an enum
+	let path = if peek_matches!(tokens, TokenType::Dot) {
+		Some(member_path(tokens, token.clone())?)
+	} else {
+		None
+	};
+
+	if let Some(binding) = match_then_consume!(tokens, TokenType::Identifier(_))
+	{
+		if in_declaration {
+			return Err(ParseError {
+				token: Some(binding),
+				message: "Cannot use type patterns in destructuring".into(),
+			});
+		}
+
+		Ok(Pattern::Type {
+			kind: path.map_or_else(
+				|| pattern_type(name, token.clone()),
+				|path| PatternType::Class(Box::new(path)),
+			),
+			binding: binding_name(binding),
+		})
+	} else if match_then_consume!(tokens, TokenType::LeftBrace).is_some() {
+		object_pattern(
+			tokens,
+			Some(path.unwrap_or_else(|| class_identifier(token))),
+			in_declaration,
+		)
+	} else if in_declaration
+		&& (path.is_some()
+			|| peek_matches!(
+				tokens,
+				TokenType::LeftSquareBracket
+					| TokenType::QuestionLeftSquareBracket
+					| TokenType::QuestionDot
+					| TokenType::LeftParen
+			)) {
+		// `o.x` or `l[0]` as a target of destructuring
+		Err(ParseError {
+			token: Some(token),
+			message: "Destructuring can only bind plain names, not properties \
+			          or indices"
+				.into(),
+		})
+	} else if let Some(path) = path {
+		// a lone path is a type pattern without a binding, so that
+		// variants without fields can be matched with just the name
+
+		Ok(Pattern::Type {
+			kind: PatternType::Class(Box::new(path)),
+			binding: None,
+		})
+	} else {
+		Ok(Pattern::Binding(binding_name(token)))
+	}
+}
+
+/// Parses a list pattern, expects the `[` to be already consumed
+fn list_pattern(
+	tokens: ParserIter,
+	in_declaration: bool,
+) -> Result<Pattern, ParseError> {
+	let mut before = Vec::new();
+	let mut rest = None;
+	let mut after = Vec::new();
+
+	while !peek_matches!(tokens, TokenType::RightSquareBracket) {
+		if let Some(dots) = match_then_consume!(tokens, TokenType::DotDotDot) {
+			if rest.is_some() {
+				return Err(ParseError {
+					token: Some(dots),
+					message: "Only one rest pattern is allowed in a list"
+						.into(),
+				});
+			}
+
+			// the rest is bound like any other binding, but to a list
+			rest = Some(Box::new(Pattern::Binding(
+				match_then_consume!(tokens, TokenType::Identifier(_))
+					.and_then(binding_name),
+			)));
+		} else if rest.is_some() {
+			after.push(parse_pattern(tokens, in_declaration)?);
+		} else {
+			before.push(parse_pattern(tokens, in_declaration)?);
+		}
+
+		if match_then_consume!(tokens, TokenType::Comma).is_none() {
+			break;
+		}
+	}
+
+	expect_one!(tokens, TokenType::RightSquareBracket)?;
+
+	Ok(Pattern::List {
+		before,
+		rest,
+		after,
+	})
+}
+
+/// Parses an object pattern, expects the `{` to be already consumed
+fn object_pattern(
+	tokens: ParserIter,
+	class: Option<Expr>,
+	in_declaration: bool,
+) -> Result<Pattern, ParseError> {
+	let mut properties = Vec::new();
+
+	while !peek_matches!(tokens, TokenType::RightBrace) {
+		let key_token = expect!(
+			tokens,
+			TokenType::Identifier(_) | TokenType::String(_),
+			"Expected property name",
+		)?;
+
+		let key = match &key_token.token_type {
+			TokenType::Identifier(s) | TokenType::String(s) => s.clone(),
+			_ => unreachable!("We just matched the key"),
+		};
+
+		let pattern = if match_then_consume!(tokens, TokenType::Colon).is_some()
+		{
+			parse_pattern(tokens, in_declaration)?
+		} else if let TokenType::Identifier(_) = key_token.token_type {
+			Pattern::Binding(binding_name(key_token))
+		} else {
+			return Err(ParseError {
+				token: Some(key_token),
+				message: "Cannot use short property pattern with string".into(),
+			});
+		};
+
+		properties.push(PropertyPattern { key, pattern });
+
+		if match_then_consume!(tokens, TokenType::Comma).is_none() {
+			break;
+		}
+	}
+
+	expect_one!(tokens, TokenType::RightBrace)?;
+
+	Ok(Pattern::Object {
+		class: class.map(Box::new),
+		properties,
+	})
+}
+
+fn literal(value: LiteralValue) -> Pattern {
+	Pattern::Literal(Box::new(Expr::Literal(value)))
+}
+
+fn pattern_type(name: &str, token: Token) -> PatternType {
+	if BUILTIN_TYPES.contains(&name) {
+		PatternType::Builtin(name.into())
+	} else {
+		PatternType::Class(Box::new(class_identifier(token)))
+	}
+}
+
+fn class_identifier(name: Token) -> Expr {
+	Expr::Identifier(IdentifierValue {
+		name,
+		env_distance: Cell::default(),
+	})
+}
+
+/// Parses a dotted path (e.g. `Shape.Circle`), expects the first name to be
+/// already consumed
+fn member_path(tokens: ParserIter, name: Token) -> Result<Expr, ParseError> {
+	let mut path = class_identifier(name);
+
+	while match_then_consume!(tokens, TokenType::Dot).is_some() {
+		let key = expect!(
+			tokens,
+			TokenType::Identifier(_),
+			"Expected a name after `.` in pattern",
+		)?;
+
+		let name = if let TokenType::Identifier(name) = &key.token_type {
+			name.clone()
+		} else {
+			unreachable!("We just matched an identifier")
+		};
+
+		path = Expr::Get(GetValue {
+			getee: Box::new(path),
+			key: GetAccessor::DotName(name),
+			blame: key,
+			optional: false,
+		});
+	}
+
+	Ok(path)
+}
+
+/// `_` matches anything, but doesn't bind it
+fn binding_name(token: Token) -> Option<Token> {
+	if matches!(&token.token_type, TokenType::Identifier(name) if &**name == "_")
+	{
+		None
+	} else {
+		Some(token)
+	}
+}
+
+/// Collects the names a pattern binds
+pub fn pattern_bindings<'a>(
+	pattern: &'a Pattern,
+	bindings: &mut Vec<&'a Token>,
+) {
+	match pattern {
+		Pattern::Binding(binding) | Pattern::Type { binding, .. } => {
+			bindings.extend(binding);
+		}
+		Pattern::List {
+			before,
+			rest,
+			after,
+		} => {
+			for element in before.iter().chain(rest.as_deref()).chain(after) {
+				pattern_bindings(element, bindings);
+			}
+		}
+		Pattern::Object { properties, .. } => {
+			for property in properties {
+				pattern_bindings(&property.pattern, bindings);
+			}
+		}
+		Pattern::Literal(_) => (),
+	}
+}
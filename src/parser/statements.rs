@@ -1,23 +1,33 @@
 use super::{
-	expressions::{expression, function_declaration},
+	expressions::{expression, method_declaration},
 	helpers::expect_semicolon,
 	parse::{declaration, statement},
+	patterns::pattern_bindings,
 	types::{ParseError, ParserIter},
 };
 use crate::{
 	ast::{
-		expr::{Expr, IdentifierValue},
+		expr::{Expr, FunctionValue, IdentifierValue},
 		stmt::{
 			BlockValue,
 			BreakValue,
+			Catch,
 			ClassValue,
 			ContinueValue,
+			EnumValue,
+			ExportValue,
 			ExpressionValue,
+			Field,
+			ForInValue,
 			ForValue,
 			IfValue,
+			ImportValue,
 			PrintValue,
 			ReturnValue,
 			Stmt,
+			ThrowValue,
+			TryValue,
+			Variant,
 		},
 	},
 	expect,
@@ -28,7 +38,7 @@ use crate::{
 	token::{Token, TokenType},
 };
 
-use std::{cell::Cell, vec};
+use std::{cell::Cell, rc::Rc, vec};
 
 
 #[inline]
@@ -44,11 +54,37 @@ pub fn print_statement(tokens: ParserIter) -> Result<Option<Stmt>, ParseError> {
 
 pub fn expression_statement(
 	tokens: ParserIter,
+	value: Option<&mut Option<Box<Expr>>>,
 ) -> Result<Option<Stmt>, ParseError> {
 	let expr = expression(tokens)?;
 
-	// expect semicolon only if the expression is not a function
-	let semicolon_expected = !matches!(expr, Expr::Function(_));
+	// an identifier followed by a colon is a loop label
+	if let Expr::Identifier(v) = &expr {
+		if match_then_consume!(tokens, TokenType::Colon).is_some() {
+			expect!(
+				tokens,
+				TokenType::For,
+				"Expected a loop after the label, only loops can be labeled",
+			)?;
+
+			return for_statement(tokens, Some(v.name.clone()));
+		}
+	}
+
+	// an unterminated expression closing a sequence becomes its value
+	if let Some(value) = value {
+		if peek_matches!(tokens, TokenType::RightBrace) {
+			*value = Some(Box::new(expr));
+
+			return Ok(None);
+		}
+	}
+
+	// expect semicolon only if the expression is not block-like
+	let semicolon_expected = !matches!(
+		expr,
+		Expr::Function(_) | Expr::Conditional(_) | Expr::Match(_)
+	);
 
 	let stmt = Stmt::Expression(ExpressionValue { expression: expr });
 
@@ -115,7 +151,15 @@ pub fn block_statement(tokens: ParserIter) -> Result<Option<Stmt>, ParseError> {
 	}
 }
 
-pub fn for_statement(tokens: ParserIter) -> Result<Option<Stmt>, ParseError> {
+pub fn for_statement(
+	tokens: ParserIter,
+	label: Option<Token>,
+) -> Result<Option<Stmt>, ParseError> {
+	// the three-field form can't start with an identifier, so that's for-in
+	if peek_matches!(tokens, TokenType::Identifier(_)) {
+		return for_in_statement(tokens, label);
+	}
+
 	// parse declaration
 	if !peek_matches!(
 		tokens,
@@ -179,6 +223,7 @@ pub fn for_statement(tokens: ParserIter) -> Result<Option<Stmt>, ParseError> {
 		closer: closer.map(|c| {
 			Box::new(Stmt::Expression(ExpressionValue { expression: c }))
 		}),
+		label,
 	});
 
 	// determine if for body requires to be in a separate block
@@ -194,6 +239,46 @@ pub fn for_statement(tokens: ParserIter) -> Result<Option<Stmt>, ParseError> {
 	Ok(Some(for_body))
 }
 
+fn for_in_statement(
+	tokens: ParserIter,
+	label: Option<Token>,
+) -> Result<Option<Stmt>, ParseError> {
+	let first =
+		expect!(tokens, TokenType::Identifier(_), "Expected identifier")?;
+
+	// with two names, the first one is the key
+	let (key, value) = if match_then_consume!(tokens, TokenType::Comma)
+		.is_some()
+	{
+		let second =
+			expect!(tokens, TokenType::Identifier(_), "Expected identifier",)?;
+
+		(Some(first), second)
+	} else {
+		(None, first)
+	};
+
+	let blame = expect_one!(tokens, TokenType::In)?;
+
+	let iterable = expression(tokens)?;
+
+	let body = match_then_consume_stmt!(
+		tokens,
+		TokenType::LeftBrace,
+		"Expected for's body"
+	)?
+	.map(Box::new);
+
+	Ok(Some(Stmt::ForIn(ForInValue {
+		key,
+		value,
+		iterable,
+		body,
+		blame,
+		label,
+	})))
+}
+
 pub fn return_statement(
 	tokens: ParserIter,
 	keyword: Token,
@@ -217,9 +302,11 @@ pub fn break_statement(
 	tokens: ParserIter,
 	keyword: Token,
 ) -> Result<Option<Stmt>, ParseError> {
+	let label = match_then_consume!(tokens, TokenType::Identifier(_));
+
 	expect_semicolon(tokens)?;
 
-	Ok(Some(Stmt::Break(BreakValue { keyword })))
+	Ok(Some(Stmt::Break(BreakValue { keyword, label })))
 }
 
 #[inline]
@@ -227,12 +314,177 @@ pub fn continue_statement(
 	tokens: ParserIter,
 	keyword: Token,
 ) -> Result<Option<Stmt>, ParseError> {
+	let label = match_then_consume!(tokens, TokenType::Identifier(_));
+
 	expect_semicolon(tokens)?;
 
-	Ok(Some(Stmt::Continue(ContinueValue { keyword })))
+	Ok(Some(Stmt::Continue(ContinueValue { keyword, label })))
 }
 
-pub fn class_statement(tokens: ParserIter) -> Result<Option<Stmt>, ParseError> {
+#[inline]
+pub fn throw_statement(
+	tokens: ParserIter,
+	keyword: Token,
+) -> Result<Option<Stmt>, ParseError> {
+	let expression = expression(tokens)?;
+
+	expect_semicolon(tokens)?;
+
+	Ok(Some(Stmt::Throw(ThrowValue {
+		keyword,
+		expression,
+	})))
+}
+
+pub fn try_statement(tokens: ParserIter) -> Result<Option<Stmt>, ParseError> {
+	let body = match_then_consume_stmt!(
+		tokens,
+		TokenType::LeftBrace,
+		"Expected a block after `try`"
+	)?
+	.map(Box::new);
+
+	let catch = if match_then_consume!(tokens, TokenType::Catch).is_some() {
+		// the parentheses around the name are optional, like in `if`s
+		let parenthesized =
+			match_then_consume!(tokens, TokenType::LeftParen).is_some();
+
+		let name = match_then_consume!(tokens, TokenType::Identifier(_));
+
+		if parenthesized {
+			expect_one!(tokens, TokenType::RightParen)?;
+		}
+
+		let body = match_then_consume_stmt!(
+			tokens,
+			TokenType::LeftBrace,
+			"Expected a block after `catch`"
+		)?
+		.map(Box::new);
+
+		Some(Catch { name, body })
+	} else {
+		None
+	};
+
+	let finally = if match_then_consume!(tokens, TokenType::Finally).is_some() {
+		match_then_consume_stmt!(
+			tokens,
+			TokenType::LeftBrace,
+			"Expected a block after `finally`"
+		)?
+		.map(Box::new)
+	} else {
+		None
+	};
+
+	if catch.is_none() && finally.is_none() {
+		return Err(ParseError {
+			message: "Expected `catch` or `finally` after the `try` block"
+				.into(),
+			token: tokens.peek().cloned(),
+		});
+	}
+
+	Ok(Some(Stmt::Try(TryValue {
+		body,
+		catch,
+		finally,
+	})))
+}
+
+pub fn import_statement(
+	tokens: ParserIter,
+) -> Result<Option<Stmt>, ParseError> {
+	let blame =
+		expect!(tokens, TokenType::String(_), "Expected a path to import")?;
+
+	let path = if let TokenType::String(path) = &blame.token_type {
+		path.clone()
+	} else {
+		unreachable!("Path should be a string \u{1f914}")
+	};
+
+	expect_one!(tokens, TokenType::As)?;
+
+	let name =
+		expect!(tokens, TokenType::Identifier(_), "Expected module's name")?;
+
+	expect_semicolon(tokens)?;
+
+	Ok(Some(Stmt::Import(ImportValue { path, name, blame })))
+}
+
+pub fn export_statement(
+	tokens: ParserIter,
+	keyword: Token,
+) -> Result<Option<Stmt>, ParseError> {
+	let mut declaration = declaration(tokens)?;
+
+	let mut names = Vec::new();
+
+	// doc comments precede the `export`, but they document the declaration
+	let keyword_doc = keyword.doc_comment();
+	let inherit_doc = |doc: &mut Option<Rc<str>>| {
+		if doc.is_none() {
+			doc.clone_from(&keyword_doc);
+		}
+	};
+
+	match &mut declaration {
+		Some(Stmt::Declaration(v)) => {
+			names.push(v.name.clone());
+			inherit_doc(&mut v.doc);
+		}
+		Some(Stmt::Destructure(v)) => {
+			let mut bindings = Vec::new();
+			pattern_bindings(&v.pattern, &mut bindings);
+
+			names.extend(bindings.into_iter().cloned());
+			inherit_doc(&mut v.doc);
+		}
+		Some(Stmt::Class(v)) => {
+			names.push(v.name.clone());
+			inherit_doc(&mut v.doc);
+		}
+		Some(Stmt::Enum(v)) => {
+			names.push(v.name.clone());
+			inherit_doc(&mut v.doc);
+		}
+		Some(Stmt::Expression(ExpressionValue {
+			expression:
+				Expr::Function(FunctionValue {
+					name: Some(name),
+					doc,
+					..
+				}),
+		})) => {
+			names.push(name.clone());
+			inherit_doc(doc);
+		}
+		_ => {
+			return Err(ParseError {
+				message: "Only declarations, named functions, classes and \
+				          enums can be exported"
+					.into(),
+				token: Some(keyword),
+			});
+		}
+	}
+
+	Ok(declaration.map(|declaration| {
+		Stmt::Export(ExportValue {
+			declaration: Box::new(declaration),
+			names,
+			keyword,
+		})
+	}))
+}
+
+pub fn class_statement(
+	tokens: ParserIter,
+	keyword: &Token,
+) -> Result<Option<Stmt>, ParseError> {
 	let name =
 		expect!(tokens, TokenType::Identifier(_), "Expected class name")?;
 
@@ -255,9 +507,81 @@ pub fn class_statement(tokens: ParserIter) -> Result<Option<Stmt>, ParseError> {
 	expect_one!(tokens, TokenType::LeftBrace)?;
 
 	let mut methods = Vec::new();
+	let mut static_methods = Vec::new();
+	let mut getters = Vec::new();
+	let mut setters = Vec::new();
+	let mut fields = Vec::new();
 
 	while !peek_matches!(tokens, TokenType::RightBrace) {
-		methods.push(function_declaration(tokens, true)?);
+		let member = expect!(
+			tokens,
+			TokenType::Identifier(_),
+			"Expected method or field name",
+		)?;
+
+		let doc = member.doc_comment();
+
+		// `static`, `get` and `set` are modifiers only when followed by a name,
+		// otherwise they are names themselves
+		let modifier = match &member.token_type {
+			TokenType::Identifier(modifier)
+				if peek_matches!(tokens, TokenType::Identifier(_)) =>
+			{
+				Some(modifier.clone())
+			}
+			_ => None,
+		};
+
+		let (name, is_static) = if let Some(modifier) = &modifier {
+			let name =
+				expect!(tokens, TokenType::Identifier(_), "Expected name",)?;
+
+			match &**modifier {
+				"static" => (name, true),
+				"get" => {
+					getters.push(accessor(tokens, name, doc, 0)?);
+					continue;
+				}
+				"set" => {
+					setters.push(accessor(tokens, name, doc, 1)?);
+					continue;
+				}
+				_ => {
+					return Err(ParseError {
+						token: Some(member),
+						message: format!("Unknown modifier `{}`", modifier),
+					})
+				}
+			}
+		} else {
+			(member, false)
+		};
+
+		if peek_matches!(tokens, TokenType::LeftParen) {
+			let method = method_declaration(tokens, name, doc)?;
+
+			if is_static {
+				static_methods.push(method);
+			} else {
+				methods.push(method);
+			}
+		} else {
+			let initializer =
+				if match_then_consume!(tokens, TokenType::Equal).is_some() {
+					Some(expression(tokens)?)
+				} else {
+					None
+				};
+
+			expect_semicolon(tokens)?;
+
+			fields.push(Field {
+				name,
+				initializer,
+				is_static,
+				doc,
+			});
+		}
 	}
 
 	expect_one!(tokens, TokenType::RightBrace)?;
@@ -265,6 +589,126 @@ pub fn class_statement(tokens: ParserIter) -> Result<Option<Stmt>, ParseError> {
 	Ok(Some(Stmt::Class(ClassValue {
 		name,
 		methods,
+		static_methods,
+		getters,
+		setters,
+		fields: Rc::new(fields),
 		superclass,
+		doc: keyword.doc_comment(),
+	})))
+}
+
+/// Parses a `get` or `set` accessor method, checking its parameter count
+pub fn enum_statement(
+	tokens: ParserIter,
+	keyword: &Token,
+) -> Result<Option<Stmt>, ParseError> {
+	let name = expect!(tokens, TokenType::Identifier(_), "Expected enum name")?;
+
+	expect_one!(tokens, TokenType::LeftBrace)?;
+
+	let mut variants: Vec<Variant> = Vec::new();
+
+	while !peek_matches!(tokens, TokenType::RightBrace) {
+		let variant_name =
+			expect!(tokens, TokenType::Identifier(_), "Expected variant name")?;
+
+		if variants.iter().any(|v| same_name(&v.name, &variant_name)) {
+			return Err(ParseError {
+				message: format!(
+					"Variant `{}` is already declared",
+					variant_name.token_type.repr()
+				),
+				token: Some(variant_name),
+			});
+		}
+
+		let fields =
+			if match_then_consume!(tokens, TokenType::LeftParen).is_some() {
+				let mut fields: Vec<Token> = Vec::new();
+
+				while !peek_matches!(tokens, TokenType::RightParen) {
+					let field = expect!(
+						tokens,
+						TokenType::Identifier(_),
+						"Expected field name",
+					)?;
+
+					if fields.iter().any(|f| same_name(f, &field)) {
+						return Err(ParseError {
+							message: format!(
+								"Field `{}` is already declared",
+								field.token_type.repr()
+							),
+							token: Some(field),
+						});
+					}
+
+					fields.push(field);
+
+					if match_then_consume!(tokens, TokenType::Comma).is_none() {
+						break;
+					}
+				}
+
+				expect_one!(tokens, TokenType::RightParen)?;
+
+				Some(fields)
+			} else {
+				None
+			};
+
+		variants.push(Variant {
+			name: variant_name,
+			fields,
+		});
+
+		if match_then_consume!(tokens, TokenType::Comma).is_none() {
+			break;
+		}
+	}
+
+	expect_one!(tokens, TokenType::RightBrace)?;
+
+	Ok(Some(Stmt::Enum(EnumValue {
+		name,
+		variants,
+		doc: keyword.doc_comment(),
 	})))
 }
+
+/// `TokenType`'s equality only compares the kinds of tokens
+fn same_name(a: &Token, b: &Token) -> bool {
+	matches!(
+		(&a.token_type, &b.token_type),
+		(TokenType::Identifier(a), TokenType::Identifier(b)) if a == b
+	)
+}
+
+fn accessor(
+	tokens: ParserIter,
+	name: Token,
+	doc: Option<Rc<str>>,
+	param_count: usize,
+) -> Result<Expr, ParseError> {
+	let method = method_declaration(tokens, name.clone(), doc)?;
+
+	let count = if let Expr::Function(fv) = &method {
+		fv.params.as_ref().map_or(0, |params| params.len())
+	} else {
+		unreachable!("Method should be a function expression")
+	};
+
+	if count == param_count {
+		Ok(method)
+	} else {
+		Err(ParseError {
+			token: Some(name),
+			message: if param_count == 0 {
+				"Getters can't have parameters".into()
+			} else {
+				"Setters need exactly one parameter".into()
+			},
+		})
+	}
+}
@@ -1,12 +1,39 @@
 use crate::{
 	ast::expr::Expr,
 	runner::DescribableError,
+	source::FileId,
 	token::{Location, Token},
 };
 
-use std::{iter, rc::Rc, vec};
+use std::{rc::Rc, vec};
 
-pub type ParserIter<'a> = &'a mut iter::Peekable<vec::IntoIter<Token>>;
+pub type ParserIter<'a> = &'a mut TokenStream;
+
+/// Like `Peekable`, but allows looking further than the next token
+pub struct TokenStream(vec::IntoIter<Token>);
+
+impl TokenStream {
+	pub fn new(tokens: Vec<Token>) -> Self {
+		Self(tokens.into_iter())
+	}
+
+	pub fn peek(&self) -> Option<&Token> {
+		self.0.as_slice().first()
+	}
+
+	/// All of the tokens that weren't consumed yet
+	pub fn ahead(&self) -> &[Token] {
+		self.0.as_slice()
+	}
+}
+
+impl Iterator for TokenStream {
+	type Item = Token;
+
+	fn next(&mut self) -> Option<Self::Item> {
+		self.0.next()
+	}
+}
 
 pub struct ParseError {
 	pub token: Option<Token>,
@@ -16,7 +43,10 @@ pub struct ParseError {
 impl DescribableError for ParseError {
 	fn location(&self) -> Location {
 		self.token.as_ref().map_or(
+			// `parse` blames the end of input for token-less errors, so this
+			// shouldn't really show up
 			Location {
+				file: FileId(0),
 				byte_offset: usize::MAX,
 				byte_length: 1,
 			},
@@ -33,6 +63,7 @@ impl Expr {
 	pub const fn human_type(&self) -> &str {
 		match self {
 			Expr::Assignment(_) => "an assignment",
+			Expr::DestructuringAssignment(_) => "a destructuring assignment",
 			Expr::Binary(_) => "a binary expression",
 			Expr::Grouping(_) => "a grouping",
 			Expr::Literal(_) => "a literal",
@@ -45,6 +76,12 @@ impl Expr {
 			Expr::This(_) => "a this expression",
 			Expr::Super(_) => "a super expression",
 			Expr::Object(_) => "an object definition",
+			Expr::Template(_) => "a template string",
+			Expr::Conditional(_) => "an if expression",
+			Expr::Sequence(_) => "a block",
+			Expr::Match(_) => "a match expression",
+			Expr::Spread(_) => "a spread",
+			Expr::OptionalChain(_) => "an optional chain",
 		}
 	}
 }
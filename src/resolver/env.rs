@@ -20,6 +20,8 @@ use std::{cell::RefCell, rc::Rc};
 pub struct ResolverEnvironment(
 	// true if variable, false if const
 	pub Rc<RefCell<EnvironmentBase<ResolverEnvironment, bool>>>,
+	// labels of the loops enclosing resolved statements
+	pub Rc<Vec<Rc<str>>>,
 );
 
 // The InterpreterValue in this implementation tells us basically nothing, as
@@ -31,13 +33,17 @@ pub struct ResolverEnvironment(
 // I'll always supply Nil here
 impl EnvironmentWrapper<InterpreterValue> for ResolverEnvironment {
 	fn new() -> Self {
-		Self(Rc::new(RefCell::new(EnvironmentBase::new(None))))
+		Self(
+			Rc::new(RefCell::new(EnvironmentBase::new(None))),
+			Rc::new(Vec::new()),
+		)
 	}
 
 	fn fork(&self) -> Self {
-		Self(Rc::new(RefCell::new(EnvironmentBase::new(Some(
-			self.clone(),
-		)))))
+		Self(
+			Rc::new(RefCell::new(EnvironmentBase::new(Some(self.clone())))),
+			self.1.clone(),
+		)
 	}
 
 	fn read(
@@ -98,15 +104,45 @@ impl EnvironmentWrapper<InterpreterValue> for ResolverEnvironment {
 		} else {
 			let name = assume_identifier(identifier);
 
-			Err(RuntimeError {
-				message: format!("Cannot reassign a const `{}`", name),
-				token: identifier.clone(),
-			})
+			Err(RuntimeError::new(
+				format!("Cannot reassign a const `{}`", name),
+				identifier.clone(),
+			))
 		}
 	}
 }
 
 impl ResolverEnvironment {
+	/// The same scope, but with `label` added to the enclosing loop labels
+	pub fn with_label(&self, label: &Token) -> Result<Self, RuntimeError> {
+		let name = assume_identifier(label);
+
+		if self.has_label(name) {
+			return Err(RuntimeError::new(
+				format!(
+					"Label `{}` is already used by an enclosing loop",
+					name
+				),
+				label.clone(),
+			));
+		}
+
+		let mut labels = (*self.1).clone();
+		labels.push(name.into());
+
+		Ok(Self(self.0.clone(), Rc::new(labels)))
+	}
+
+	/// The same scope, but without any loop labels, as jumps can't leave
+	/// function bodies
+	pub fn without_labels(&self) -> Self {
+		Self(self.0.clone(), Rc::new(Vec::new()))
+	}
+
+	pub fn has_label(&self, name: &str) -> bool {
+		self.1.iter().any(|label| &**label == name)
+	}
+
 	pub fn exists(&self, name: &str) -> bool {
 		unwrap_scope!(self).get(name).is_some()
 			|| unwrap_enclosing!(self)
@@ -145,10 +181,10 @@ impl ResolverEnvironment {
 
 			Ok(())
 		} else {
-			Err(RuntimeError {
-				token: resolvable_token.clone(),
-				message: format!("Identifier `{}` not defined", name),
-			})
+			Err(RuntimeError::new(
+				format!("Identifier `{}` not defined", name),
+				resolvable_token.clone(),
+			))
 		}
 	}
 }
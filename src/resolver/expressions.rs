@@ -4,22 +4,31 @@ use crate::{
 		AssignmentValue,
 		BinaryValue,
 		CallValue,
+		ConditionalValue,
+		DestructuringAssignmentValue,
 		Expr,
 		FunctionValue,
 		GetAccessor,
 		GetValue,
 		IdentifierValue,
+		LiteralValue,
+		MatchValue,
 		ObjectValue,
+		Pattern,
+		PatternType,
+		SequenceValue,
 		SetValue,
 		SuperAccessor,
 		SuperValue,
+		TemplateValue,
 		ThisValue,
 	},
 	env::{DeclaredValue, EnvironmentWrapper},
 	interpreter::{
-		helpers::{assume_identifier, guard_function},
+		helpers::{assume_identifier, guard_function, guard_sequence},
 		types::{InterpreterValue, RuntimeError},
 	},
+	token::Token,
 };
 
 
@@ -50,6 +59,27 @@ pub fn assignment_expression(
 	Ok(InterpreterValue::Nil)
 }
 
+pub fn destructuring_assignment_expression(
+	v: &DestructuringAssignmentValue,
+	env: &ResolverEnvironment,
+) -> Result<InterpreterValue, RuntimeError> {
+	resolve::expression(&v.value, env)?;
+
+	// pattern's classes need resolving too, its bindings are only assigned to
+	// so they get declared in a throwaway scope
+	resolve_pattern(&v.pattern, false, env, &env.fork())?;
+
+	for target in &v.targets {
+		if let Expr::Identifier(t) = target {
+			env.resolve_nest_level(target, &t.name)?;
+
+			env.assign(t.env_distance.get(), &t.name, InterpreterValue::Nil)?;
+		}
+	}
+
+	Ok(InterpreterValue::Nil)
+}
+
 pub fn function_expression(
 	v: &FunctionValue,
 	env: &ResolverEnvironment,
@@ -59,13 +89,10 @@ pub fn function_expression(
 		let iden = assume_identifier(name);
 
 		if env.exists(iden) {
-			return Err(RuntimeError {
-				message: format!(
-					"A value with name `{}` is already in the scope",
-					iden,
-				),
-				token: name.clone(),
-			});
+			return Err(RuntimeError::new(
+				format!("A value with name `{}` is already in the scope", iden),
+				name.clone(),
+			));
 		}
 
 		env.declare(
@@ -77,21 +104,24 @@ pub fn function_expression(
 		);
 	}
 
-	let new_scope = env.fork();
+	let new_scope = env.fork().without_labels();
 
-	// declaring dummy for each parameter
+	// declaring dummy for each parameter, defaults can refer to the previous
+	// ones
 	if let Some(params) = &v.params {
-		params.iter().for_each(|param| {
-			let name = assume_identifier(param);
+		for param in params.iter() {
+			if let Some(default) = &param.default {
+				resolve::expression(default, &new_scope)?;
+			}
 
 			new_scope.declare(
-				name.to_owned(),
+				assume_identifier(&param.name).to_owned(),
 				DeclaredValue {
 					mutable: true,
 					value: InterpreterValue::Nil,
 				},
 			);
-		});
+		}
 	}
 
 	// evaluating function body
@@ -127,6 +157,19 @@ pub fn call_expression(
 	Ok(InterpreterValue::Nil)
 }
 
+pub fn literal_expression(
+	v: &LiteralValue,
+	env: &ResolverEnvironment,
+) -> Result<InterpreterValue, RuntimeError> {
+	if let LiteralValue::List(values) = v {
+		for value in values.iter() {
+			resolve::expression(value, env)?;
+		}
+	}
+
+	Ok(InterpreterValue::Nil)
+}
+
 #[inline]
 pub fn get_expression(
 	v: &GetValue,
@@ -134,15 +177,7 @@ pub fn get_expression(
 ) -> Result<InterpreterValue, RuntimeError> {
 	resolve::expression(&v.getee, env)?;
 
-	match &v.key {
-		GetAccessor::DotEval(key) => {
-			resolve::expression(key, env)?;
-		}
-		GetAccessor::SubscriptionEval(expr) => {
-			resolve::expression(expr, env)?;
-		}
-		_ => (),
-	}
+	accessor(&v.key, env)?;
 
 	Ok(InterpreterValue::Nil)
 }
@@ -154,24 +189,43 @@ pub fn set_expression(
 	resolve::expression(&v.setee, env)?;
 	resolve::expression(&v.value, env)?;
 
-	if let GetAccessor::DotEval(key) = &v.key {
-		resolve::expression(key, env)?;
-	}
+	accessor(&v.key, env)?;
 
 	Ok(InterpreterValue::Nil)
 }
 
+/// Resolves the expressions a get or a set accessor consists of
+fn accessor(
+	key: &GetAccessor,
+	env: &ResolverEnvironment,
+) -> Result<(), RuntimeError> {
+	match key {
+		GetAccessor::DotEval(expr) | GetAccessor::SubscriptionEval(expr) => {
+			resolve::expression(expr, env)?;
+		}
+		GetAccessor::Slice { start, end, step } => {
+			for bound in [start, end, step].iter().copied().flatten() {
+				resolve::expression(bound, env)?;
+			}
+		}
+		GetAccessor::DotName(_) | GetAccessor::SubscriptionNumber(_) => (),
+	}
+
+	Ok(())
+}
+
 #[inline]
 pub fn this_expression(
 	expr: &Expr,
 	v: &ThisValue,
 	env: &ResolverEnvironment,
 ) -> Result<InterpreterValue, RuntimeError> {
-	env.resolve_nest_level(expr, &v.blame)
-		.map_err(|err| RuntimeError {
-			token: err.token,
-			message: "Cannot call `this` outside of a method".into(),
-		})?;
+	env.resolve_nest_level(expr, &v.blame).map_err(|err| {
+		RuntimeError::new(
+			"Cannot call `this` outside of a method".into(),
+			err.token,
+		)
+	})?;
 
 	Ok(InterpreterValue::Nil)
 }
@@ -181,12 +235,12 @@ pub fn super_expression(
 	v: &SuperValue,
 	env: &ResolverEnvironment,
 ) -> Result<InterpreterValue, RuntimeError> {
-	env.resolve_nest_level(expr, &v.blame)
-		.map_err(|err| RuntimeError {
-			token: err.token,
-			message: "Cannot call `super` outside of a child class method"
-				.into(),
-		})?;
+	env.resolve_nest_level(expr, &v.blame).map_err(|err| {
+		RuntimeError::new(
+			"Cannot call `super` outside of a child class method".into(),
+			err.token,
+		)
+	})?;
 
 	if let SuperAccessor::Call(args) = &v.accessor {
 		for arg in args.iter() {
@@ -208,3 +262,129 @@ pub fn object_expression(
 
 	Ok(InterpreterValue::Nil)
 }
+
+#[inline]
+pub fn template_expression(
+	v: &TemplateValue,
+	env: &ResolverEnvironment,
+) -> Result<InterpreterValue, RuntimeError> {
+	for part in &v.parts {
+		resolve::expression(part, env)?;
+	}
+
+	Ok(InterpreterValue::Nil)
+}
+
+pub fn conditional_expression(
+	v: &ConditionalValue,
+	env: &ResolverEnvironment,
+) -> Result<InterpreterValue, RuntimeError> {
+	resolve::expression(&v.condition, env)?;
+	resolve::expression(&v.then, env)?;
+
+	if let Some(otherwise) = &v.otherwise {
+		resolve::expression(otherwise, env)?;
+	}
+
+	Ok(InterpreterValue::Nil)
+}
+
+pub fn sequence_expression(
+	v: &SequenceValue,
+	env: &ResolverEnvironment,
+) -> Result<InterpreterValue, RuntimeError> {
+	let new_scope = env.fork();
+
+	guard_sequence(resolve::statements(&v.statements, &new_scope)?)?;
+
+	if let Some(value) = &v.value {
+		resolve::expression(value, &new_scope)?;
+	}
+
+	Ok(InterpreterValue::Nil)
+}
+
+pub fn match_expression(
+	v: &MatchValue,
+	env: &ResolverEnvironment,
+) -> Result<InterpreterValue, RuntimeError> {
+	resolve::expression(&v.value, env)?;
+
+	for arm in &v.arms {
+		// bindings live in a scope of their own arm
+		let arm_env = env.fork();
+
+		resolve_pattern(&arm.pattern, false, env, &arm_env)?;
+
+		if let Some(guard) = &arm.guard {
+			resolve::expression(guard, &arm_env)?;
+		}
+
+		resolve::expression(&arm.body, &arm_env)?;
+	}
+
+	Ok(InterpreterValue::Nil)
+}
+
+/// Declares pattern's bindings in `arm_env`, while classes it refers to are
+/// resolved in `env`
+pub fn resolve_pattern(
+	pattern: &Pattern,
+	mutable: bool,
+	env: &ResolverEnvironment,
+	arm_env: &ResolverEnvironment,
+) -> Result<(), RuntimeError> {
+	let declare_binding = |binding: &Option<Token>| {
+		if let Some(binding) = binding {
+			arm_env.declare(
+				assume_identifier(binding).to_owned(),
+				DeclaredValue {
+					mutable,
+					value: InterpreterValue::Nil,
+				},
+			);
+		}
+	};
+
+	match pattern {
+		Pattern::Literal(expr) => {
+			resolve::expression(expr, env)?;
+		}
+		Pattern::Binding(binding) => declare_binding(binding),
+		Pattern::Type { kind, binding } => {
+			if let PatternType::Class(class) = kind {
+				resolve::expression(class, env)?;
+			}
+
+			declare_binding(binding);
+		}
+		Pattern::List {
+			before,
+			rest,
+			after,
+		} => {
+			for element in before {
+				resolve_pattern(element, mutable, env, arm_env)?;
+			}
+
+			if let Some(rest) = rest {
+				resolve_pattern(rest, mutable, env, arm_env)?;
+			}
+
+			for element in after {
+				resolve_pattern(element, mutable, env, arm_env)?;
+			}
+		}
+		Pattern::Object { class, properties } => {
+			if let Some(class) = class {
+				resolve::expression(class, env)?;
+			}
+
+			for property in properties {
+				resolve_pattern(&property.pattern, mutable, env, arm_env)?;
+			}
+		}
+	}
+
+	Ok(())
+}
@@ -4,20 +4,34 @@ use super::{
 		assignment_expression,
 		binary_expression,
 		call_expression,
+		conditional_expression,
+		destructuring_assignment_expression,
 		function_expression,
 		get_expression,
 		identifier_expression,
+		literal_expression,
+		match_expression,
 		object_expression,
+		sequence_expression,
 		set_expression,
 		super_expression,
+		template_expression,
 		this_expression,
 	},
 	statements::{
 		class_statement,
 		declaration_statement,
+		destructure_statement,
+		enum_statement,
+		export_statement,
+		for_in_statement,
 		for_statement,
 		if_statement,
+		import_statement,
+		jump_statement,
 		print_statement,
+		throw_statement,
+		try_statement,
 	},
 };
 use crate::{
@@ -46,18 +60,18 @@ pub fn resolve(stmts: &[Stmt]) -> Result<(), RuntimeError> {
 
 	match statements(stmts, &scope)? {
 		StmtResult::Noop => Ok(()),
-		StmtResult::Break(token) => Err(RuntimeError {
-			message: "Cannot use `break` outside of a loop".into(),
+		StmtResult::Break { keyword: token, .. } => Err(RuntimeError::new(
+			"Cannot use `break` outside of a loop".into(),
 			token,
-		}),
-		StmtResult::Continue(token) => Err(RuntimeError {
-			message: "Cannot use `continue` outside of a loop".into(),
+		)),
+		StmtResult::Continue { keyword: token, .. } => Err(RuntimeError::new(
+			"Cannot use `continue` outside of a loop".into(),
 			token,
-		}),
-		StmtResult::Return { keyword, .. } => Err(RuntimeError {
-			message: "Cannot use `return` outside of a function".into(),
-			token: keyword,
-		}),
+		)),
+		StmtResult::Return { keyword, .. } => Err(RuntimeError::new(
+			"Cannot use `return` outside of a function".into(),
+			keyword,
+		)),
 	}
 }
 
@@ -87,8 +101,16 @@ pub fn statement(
 		Stmt::Expression(v) => {
 			interpreter_stmts::expression_statement(expression, v, env)
 		}
-		Stmt::Break(v) => Ok(interpreter_stmts::break_statement(v)),
-		Stmt::Continue(v) => Ok(interpreter_stmts::continue_statement(v)),
+		Stmt::Break(v) => {
+			jump_statement(&v.keyword, v.label.as_ref(), env)?;
+
+			Ok(interpreter_stmts::break_statement(v))
+		}
+		Stmt::Continue(v) => {
+			jump_statement(&v.keyword, v.label.as_ref(), env)?;
+
+			Ok(interpreter_stmts::continue_statement(v))
+		}
 		Stmt::Return(v) => {
 			interpreter_stmts::return_statement(expression, v, env)
 		}
@@ -96,9 +118,16 @@ pub fn statement(
 		// custom resolver statement handlers
 		Stmt::Print(v) => print_statement(v, env),
 		Stmt::Declaration(v) => declaration_statement(v, env),
+		Stmt::Destructure(v) => destructure_statement(v, env),
 		Stmt::If(v) => if_statement(v, env),
 		Stmt::For(v) => for_statement(v, env),
+		Stmt::ForIn(v) => for_in_statement(v, env),
 		Stmt::Class(v) => class_statement(v, env),
+		Stmt::Enum(v) => Ok(enum_statement(v, env)),
+		Stmt::Throw(v) => throw_statement(v, env),
+		Stmt::Try(v) => try_statement(v, env),
+		Stmt::Import(v) => Ok(import_statement(v, env)),
+		Stmt::Export(v) => export_statement(v, env),
 	}
 }
 
@@ -108,19 +137,28 @@ pub fn expression(
 ) -> Result<InterpreterValue, RuntimeError> {
 	match expr {
 		Expr::Grouping(v) => expression(&v.expression, env),
-		Expr::Literal(_v) => Ok(InterpreterValue::Nil),
+		Expr::Literal(v) => literal_expression(v, env),
 
 		// custom resolver expression handlers
 		Expr::Identifier(v) => identifier_expression(expr, v, env),
 		Expr::Assignment(v) => assignment_expression(expr, v, env),
+		Expr::DestructuringAssignment(v) => {
+			destructuring_assignment_expression(v, env)
+		}
 		Expr::Unary(v) => expression(&v.right, env),
 		Expr::Function(v) => function_expression(v, env),
 		Expr::Super(v) => super_expression(expr, v, env),
 		Expr::This(v) => this_expression(expr, v, env),
 		Expr::Binary(v) => binary_expression(v, env),
 		Expr::Object(v) => object_expression(v, env),
+		Expr::Template(v) => template_expression(v, env),
 		Expr::Call(v) => call_expression(v, env),
 		Expr::Get(v) => get_expression(v, env),
 		Expr::Set(v) => set_expression(v, env),
+		Expr::Conditional(v) => conditional_expression(v, env),
+		Expr::Sequence(v) => sequence_expression(v, env),
+		Expr::Match(v) => match_expression(v, env),
+		Expr::Spread(v) => expression(&v.value, env),
+		Expr::OptionalChain(v) => expression(&v.expression, env),
 	}
 }
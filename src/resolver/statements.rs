@@ -1,14 +1,29 @@
-use super::{env::ResolverEnvironment, resolve};
+use super::{env::ResolverEnvironment, expressions::resolve_pattern, resolve};
 use crate::{
 	ast::{
 		expr::Expr,
-		stmt::{ClassValue, DeclarationValue, ForValue, IfValue, PrintValue},
+		stmt::{
+			ClassValue,
+			DeclarationValue,
+			DestructureValue,
+			EnumValue,
+			ExportValue,
+			ForInValue,
+			ForValue,
+			IfValue,
+			ImportValue,
+			PrintValue,
+			ThrowValue,
+			TryValue,
+		},
 	},
 	env::{DeclaredValue, EnvironmentWrapper},
 	interpreter::{
 		helpers::assume_identifier,
 		types::{InterpreterValue, RuntimeError, StmtResult},
 	},
+	token::Token,
+	unwrap_enclosing,
 };
 
 
@@ -41,6 +56,17 @@ pub fn declaration_statement(
 	Ok(StmtResult::Noop)
 }
 
+pub fn destructure_statement(
+	v: &DestructureValue,
+	env: &ResolverEnvironment,
+) -> Result<StmtResult<InterpreterValue>, RuntimeError> {
+	resolve::expression(&v.initializer, env)?;
+
+	resolve_pattern(&v.pattern, v.mutable, env, env)?;
+
+	Ok(StmtResult::Noop)
+}
+
 pub fn if_statement(
 	v: &IfValue,
 	env: &ResolverEnvironment,
@@ -62,6 +88,8 @@ pub fn for_statement(
 	v: &ForValue,
 	env: &ResolverEnvironment,
 ) -> Result<StmtResult<InterpreterValue>, RuntimeError> {
+	let env = &loop_env(v.label.as_ref(), env)?;
+
 	resolve::statement(&v.body, env)?;
 
 	if let Some(condition) = &v.condition {
@@ -75,6 +103,152 @@ pub fn for_statement(
 	Ok(StmtResult::Noop)
 }
 
+pub fn for_in_statement(
+	v: &ForInValue,
+	env: &ResolverEnvironment,
+) -> Result<StmtResult<InterpreterValue>, RuntimeError> {
+	resolve::expression(&v.iterable, env)?;
+
+	// mirrors the scope the interpreter creates for every iteration
+	let iteration_env = loop_env(v.label.as_ref(), env)?.fork();
+
+	for name in v.key.iter().chain(std::iter::once(&v.value)) {
+		iteration_env.declare(
+			assume_identifier(name).to_owned(),
+			DeclaredValue {
+				mutable: false,
+				value: InterpreterValue::Nil,
+			},
+		);
+	}
+
+	if let Some(body) = &v.body {
+		resolve::statement(body, &iteration_env)?;
+	}
+
+	Ok(StmtResult::Noop)
+}
+
+#[inline]
+pub fn throw_statement(
+	v: &ThrowValue,
+	env: &ResolverEnvironment,
+) -> Result<StmtResult<InterpreterValue>, RuntimeError> {
+	resolve::expression(&v.expression, env)?;
+
+	Ok(StmtResult::Noop)
+}
+
+pub fn try_statement(
+	v: &TryValue,
+	env: &ResolverEnvironment,
+) -> Result<StmtResult<InterpreterValue>, RuntimeError> {
+	if let Some(body) = &v.body {
+		resolve::statement(body, env)?;
+	}
+
+	if let Some(catch) = &v.catch {
+		// mirrors the scope the interpreter binds the caught value in
+		let catch_env = env.fork();
+
+		if let Some(name) = &catch.name {
+			catch_env.declare(
+				assume_identifier(name).to_owned(),
+				DeclaredValue {
+					mutable: false,
+					value: InterpreterValue::Nil,
+				},
+			);
+		}
+
+		if let Some(body) = &catch.body {
+			resolve::statement(body, &catch_env)?;
+		}
+	}
+
+	if let Some(finally) = &v.finally {
+		resolve::statement(finally, env)?;
+	}
+
+	Ok(StmtResult::Noop)
+}
+
+#[inline]
+pub fn import_statement(
+	v: &ImportValue,
+	env: &ResolverEnvironment,
+) -> StmtResult<InterpreterValue> {
+	env.declare(
+		assume_identifier(&v.name).to_owned(),
+		DeclaredValue {
+			mutable: false,
+			value: InterpreterValue::Nil,
+		},
+	);
+
+	StmtResult::Noop
+}
+
+pub fn export_statement(
+	v: &ExportValue,
+	env: &ResolverEnvironment,
+) -> Result<StmtResult<InterpreterValue>, RuntimeError> {
+	// exports are read from the module's scope after it's evaluated
+	if unwrap_enclosing!(env).is_some() {
+		return Err(RuntimeError::new(
+			"Exports are only allowed at the top level of a module".into(),
+			v.keyword.clone(),
+		));
+	}
+
+	resolve::statement(&v.declaration, env)
+}
+
+/// Env of a loop's body, with the loop's label (if any) being available
+fn loop_env(
+	label: Option<&Token>,
+	env: &ResolverEnvironment,
+) -> Result<ResolverEnvironment, RuntimeError> {
+	label.map_or_else(|| Ok(env.clone()), |label| env.with_label(label))
+}
+
+#[inline]
+pub fn jump_statement(
+	keyword: &Token,
+	label: Option<&Token>,
+	env: &ResolverEnvironment,
+) -> Result<(), RuntimeError> {
+	match label {
+		Some(label) if !env.has_label(assume_identifier(label)) => {
+			Err(RuntimeError::new(
+				format!(
+					"Cannot `{}` to an unknown label `{}`",
+					keyword.token_type,
+					assume_identifier(label)
+				),
+				label.clone(),
+			))
+		}
+		_ => Ok(()),
+	}
+}
+
+#[inline]
+pub fn enum_statement(
+	v: &EnumValue,
+	env: &ResolverEnvironment,
+) -> StmtResult<InterpreterValue> {
+	env.declare(
+		assume_identifier(&v.name).to_owned(),
+		DeclaredValue {
+			mutable: false,
+			value: InterpreterValue::Nil,
+		},
+	);
+
+	StmtResult::Noop
+}
+
 pub fn class_statement(
 	v: &ClassValue,
 	env: &ResolverEnvironment,
@@ -99,10 +273,10 @@ pub fn class_statement(
 		let super_iden = assume_identifier(&superclass.name);
 
 		if super_iden == iden {
-			return Err(RuntimeError {
-				message: "Class cannot inherit from itself".into(),
-				token: superclass.name.clone(),
-			});
+			return Err(RuntimeError::new(
+				"Class cannot inherit from itself".into(),
+				superclass.name.clone(),
+			));
 		}
 
 		resolve::expression(expr, env)?;
@@ -122,19 +296,38 @@ pub fn class_statement(
 		None
 	};
 
-	let class_env = superclass_env.map_or_else(|| env.fork(), |dce| dce.fork());
+	// every kind of methods gets an env of its own, so that method names
+	// don't clash (e.g. a getter and a setter of the same name), but all of
+	// them are on the same depth, with `this` bound
+	let method_env = || {
+		let method_env = superclass_env
+			.as_ref()
+			.map_or_else(|| env.fork(), EnvironmentWrapper::fork);
 
-	class_env.declare(
-		"this".to_owned(),
-		DeclaredValue {
-			mutable: false,
-			value: InterpreterValue::Nil,
-		},
-	);
+		method_env.declare(
+			"this".to_owned(),
+			DeclaredValue {
+				mutable: false,
+				value: InterpreterValue::Nil,
+			},
+		);
+
+		method_env
+	};
+
+	for methods in [&v.methods, &v.static_methods, &v.getters, &v.setters] {
+		let class_env = method_env();
+
+		for method in methods {
+			// expression wires the method to function_expression
+			resolve::expression(method, &class_env)?;
+		}
+	}
+
+	let field_env = method_env();
 
-	for method in &v.methods {
-		// expression wires the method to function_expression
-		resolve::expression(method, &class_env)?;
+	for initializer in v.fields.iter().filter_map(|f| f.initializer.as_ref()) {
+		resolve::expression(initializer, &field_env)?;
 	}
 
 	Ok(StmtResult::Noop)
@@ -0,0 +1,340 @@
+use crate::{
+	ast::{
+		expr::{Expr, FunctionValue, LiteralValue, Parameter},
+		stmt::{
+			ClassValue,
+			DeclarationValue,
+			DestructureValue,
+			EnumValue,
+			ExportValue,
+			ExpressionValue,
+			Stmt,
+		},
+	},
+	parser::pattern_bindings,
+	token::{Token, TokenType},
+};
+
+use std::{fmt::Write, rc::Rc};
+
+
+struct Item {
+	title: String,
+	signature: String,
+	doc: Option<Rc<str>>,
+	/// class methods are nested under their class
+	nested: bool,
+}
+
+/// Renders Markdown documentation of the script's public API
+///
+/// public API consists of top level declarations, named functions, classes
+/// (with their methods) and enums, whose names don't start with `_`. Modules
+/// (scripts with any `export`) only have the exported ones in it
+pub fn generate(title: &str, statements: &[Stmt]) -> String {
+	let is_module = statements.iter().any(|s| matches!(s, Stmt::Export(_)));
+
+	let items = collect_items(statements, is_module);
+
+	let mut markdown = format!("# {}\n\n\n", title);
+
+	if !items.is_empty() {
+		markdown += "---\n";
+
+		for item in &items {
+			let _ = writeln!(
+				markdown,
+				"{}* [{}](#{})",
+				if item.nested { "\t" } else { "" },
+				item.title,
+				anchor(&item.title)
+			);
+		}
+
+		markdown += "---\n\n\n";
+	}
+
+	for item in &items {
+		let _ = write!(
+			markdown,
+			"{} {}\nSignature: `{}`\n\n",
+			if item.nested { "###" } else { "##" },
+			item.title,
+			item.signature
+		);
+
+		if let Some(doc) = &item.doc {
+			let _ = write!(markdown, "{}\n\n", doc);
+		}
+
+		markdown += "\n";
+	}
+
+	markdown
+}
+
+fn collect_items(statements: &[Stmt], exported_only: bool) -> Vec<Item> {
+	let mut items = Vec::new();
+
+	for stmt in statements {
+		if exported_only && !matches!(stmt, Stmt::Export(_)) {
+			continue;
+		}
+
+		match stmt {
+			Stmt::Declaration(DeclarationValue {
+				name,
+				initializer,
+				mutable,
+				doc,
+			}) => {
+				let name = identifier_name(name);
+
+				if is_private(name) {
+					continue;
+				}
+
+				let keyword = if *mutable { "let" } else { "const" };
+
+				let signature = if let Some(Expr::Function(fv)) = initializer {
+					format!("{} {} = fun({})", keyword, name, params(fv))
+				} else {
+					format!("{} {}", keyword, name)
+				};
+
+				items.push(Item {
+					title: name.to_owned(),
+					signature,
+					doc: doc.clone(),
+					nested: false,
+				});
+			}
+			Stmt::Expression(ExpressionValue {
+				expression: Expr::Function(fv),
+			}) => {
+				let name = if let Some(name) = &fv.name {
+					identifier_name(name)
+				} else {
+					continue;
+				};
+
+				if is_private(name) {
+					continue;
+				}
+
+				items.push(Item {
+					title: name.to_owned(),
+					signature: format!("fun {}({})", name, params(fv)),
+					doc: fv.doc.clone(),
+					nested: false,
+				});
+			}
+			Stmt::Destructure(DestructureValue {
+				pattern,
+				mutable,
+				doc,
+				..
+			}) => {
+				let keyword = if *mutable { "let" } else { "const" };
+
+				let mut bindings = Vec::new();
+				pattern_bindings(pattern, &mut bindings);
+
+				for name in bindings.into_iter().map(identifier_name) {
+					if is_private(name) {
+						continue;
+					}
+
+					items.push(Item {
+						title: name.to_owned(),
+						signature: format!("{} {}", keyword, name),
+						doc: doc.clone(),
+						nested: false,
+					});
+				}
+			}
+			Stmt::Class(cv) => class_items(cv, &mut items),
+			Stmt::Enum(ev) => enum_items(ev, &mut items),
+			Stmt::Export(ExportValue { declaration, .. }) => {
+				items.extend(collect_items(
+					std::slice::from_ref(declaration),
+					false,
+				));
+			}
+			_ => (),
+		}
+	}
+
+	items
+}
+
+fn class_items(cv: &ClassValue, items: &mut Vec<Item>) {
+	let class_name = identifier_name(&cv.name);
+
+	if is_private(class_name) {
+		return;
+	}
+
+	let signature = if let Some(Expr::Identifier(superclass)) = &cv.superclass {
+		format!(
+			"class {} extends {}",
+			class_name,
+			identifier_name(&superclass.name)
+		)
+	} else {
+		format!("class {}", class_name)
+	};
+
+	items.push(Item {
+		title: class_name.to_owned(),
+		signature,
+		doc: cv.doc.clone(),
+		nested: false,
+	});
+
+	for field in cv.fields.iter() {
+		let name = identifier_name(&field.name);
+
+		if is_private(name) {
+			continue;
+		}
+
+		items.push(Item {
+			title: format!("{}.{}", class_name, name),
+			signature: if field.is_static {
+				format!("static {}", name)
+			} else {
+				name.to_owned()
+			},
+			doc: field.doc.clone(),
+			nested: true,
+		});
+	}
+
+	let methods = cv
+		.methods
+		.iter()
+		.map(|method| ("", method))
+		.chain(cv.static_methods.iter().map(|method| ("static ", method)))
+		.chain(cv.getters.iter().map(|method| ("get ", method)))
+		.chain(cv.setters.iter().map(|method| ("set ", method)));
+
+	for (modifier, method) in methods {
+		let fv = if let Expr::Function(fv) = method {
+			fv
+		} else {
+			continue;
+		};
+
+		let name = identifier_name(&fv.keyword);
+
+		if is_private(name) {
+			continue;
+		}
+
+		items.push(Item {
+			title: format!("{}.{}", class_name, name),
+			signature: format!("{}{}({})", modifier, name, params(fv)),
+			doc: fv.doc.clone(),
+			nested: true,
+		});
+	}
+}
+
+fn enum_items(ev: &EnumValue, items: &mut Vec<Item>) {
+	let enum_name = identifier_name(&ev.name);
+
+	if is_private(enum_name) {
+		return;
+	}
+
+	items.push(Item {
+		title: enum_name.to_owned(),
+		signature: format!("enum {}", enum_name),
+		doc: ev.doc.clone(),
+		nested: false,
+	});
+
+	for variant in &ev.variants {
+		let name = identifier_name(&variant.name);
+
+		let signature = variant.fields.as_ref().map_or_else(
+			|| name.to_owned(),
+			|fields| {
+				format!(
+					"{}({})",
+					name,
+					fields
+						.iter()
+						.map(identifier_name)
+						.collect::<Vec<&str>>()
+						.join(", ")
+				)
+			},
+		);
+
+		items.push(Item {
+			title: format!("{}.{}", enum_name, name),
+			signature,
+			doc: None,
+			nested: true,
+		});
+	}
+}
+
+fn params(fv: &FunctionValue) -> String {
+	fv.params.as_ref().map_or_else(String::new, |params| {
+		params.iter().map(param).collect::<Vec<String>>().join(", ")
+	})
+}
+
+fn param(param: &Parameter) -> String {
+	let name = identifier_name(&param.name);
+
+	if param.rest {
+		format!("...{}", name)
+	} else if let Some(default) = &param.default {
+		format!("{} = {}", name, default_repr(default))
+	} else {
+		name.to_owned()
+	}
+}
+
+/// Only literal default values are shown, other ones are elided
+fn default_repr(default: &Expr) -> String {
+	match default {
+		Expr::Literal(LiteralValue::String(s)) => format!("{:?}", s),
+		Expr::Literal(LiteralValue::Number(n)) => format!("{}", n),
+		Expr::Literal(LiteralValue::Char(c)) => format!("{:?}", c),
+		Expr::Literal(LiteralValue::True) => "true".into(),
+		Expr::Literal(LiteralValue::False) => "false".into(),
+		Expr::Literal(LiteralValue::Nil) => "nil".into(),
+		_ => "…".into(),
+	}
+}
+
+fn identifier_name(token: &Token) -> &str {
+	if let TokenType::Identifier(name) = &token.token_type {
+		name
+	} else {
+		unreachable!("Expected an identifier token")
+	}
+}
+
+fn is_private(name: &str) -> bool {
+	name.starts_with('_')
+}
+
+/// Mimics the anchors generated for headings by GitHub
+fn anchor(title: &str) -> String {
+	title
+		.chars()
+		.filter_map(|c| match c {
+			' ' => Some('-'),
+			c if c.is_alphanumeric() || c == '_' || c == '-' => {
+				Some(c.to_ascii_lowercase())
+			}
+			_ => None,
+		})
+		.collect()
+}
@@ -1,5 +1,5 @@
 use super::helpers::get_line;
-use crate::token::Location;
+use crate::{source::SourceMap, token::Location};
 
 use std::{fmt, io::Write};
 use termcolor::{Color, ColorChoice, ColorSpec, StandardStream, WriteColor};
@@ -11,7 +11,7 @@ pub trait DescribableError {
 }
 
 
-pub fn report<T>(source: &str, category: &str, errors: &[T])
+pub fn report<T>(files: &SourceMap, category: &str, errors: &[T])
 where
 	T: DescribableError,
 {
@@ -21,18 +21,18 @@ where
 		if errors.len() > 1 { "s" } else { "" }
 	);
 
-	errors.iter().for_each(|error| report_error(source, error));
+	errors.iter().for_each(|error| report_error(files, error));
 
 	println!();
 }
 
-fn report_error<T>(source: &str, error: &T)
+fn report_error<T>(files: &SourceMap, error: &T)
 where
 	T: DescribableError,
 {
 	let location = error.location();
 
-	let line = get_line(source, location.byte_offset);
+	let line = get_line(files.get(location.file), location.byte_offset);
 	let line_prefix = line.prefix();
 
 	let trimmed_content = line.content.trim_start();
@@ -1,29 +1,15 @@
 use super::types::Line;
+use crate::source::SourceFile;
 
 
-pub fn get_line(source: &str, byte_offset: usize) -> Line {
-	let mut line_start_offset = 0;
-	let mut line_end_offset = source.len();
-	let mut lines = 1;
-
-	// getting the start and end of the line
-	for (i, c) in source.as_bytes().iter().enumerate() {
-		if *c == b'\n' {
-			if i < byte_offset {
-				line_start_offset = i + 1;
-			} else {
-				line_end_offset = i;
-				break;
-			}
-
-			lines += 1;
-		}
-	}
+pub fn get_line(file: &SourceFile, byte_offset: usize) -> Line {
+	let span = file.line(byte_offset);
 
 	Line {
-		content: source[line_start_offset..line_end_offset].to_string(),
-		number: lines,
-		offset: (byte_offset - line_start_offset + 1)
-			.min(line_end_offset - line_start_offset),
+		file_name: file.name.clone(),
+		content: file.source[span.start..span.end].to_string(),
+		number: span.number,
+		offset: (byte_offset.saturating_sub(span.start) + 1)
+			.min(span.end - span.start + 1),
 	}
 }
@@ -1,8 +1,14 @@
+mod doc;
 mod errors;
 mod helpers;
 mod run;
 mod types;
 
 pub use errors::DescribableError;
-pub use run::{file as run_file, repl as run_repl, source as run_source};
+pub use run::{
+	doc as run_doc,
+	file as run_file,
+	repl as run_repl,
+	source as run_source,
+};
 pub use types::RunError;
@@ -1,9 +1,18 @@
-use super::{errors, types::RunError};
-use crate::{interpreter, parser, resolver, scanner};
+use super::{doc, errors, types::RunError};
+use crate::{
+	ast::stmt::Stmt,
+	interpreter,
+	parser,
+	resolver,
+	scanner,
+	source::{FileId, SourceMap},
+};
 
 use std::{
+	cell::RefCell,
 	fs,
 	io::{self, Read, Write},
+	rc::Rc,
 };
 
 
@@ -19,17 +28,46 @@ pub fn file(path: &str) -> Result<(), RunError> {
 	let mut buffer = String::new();
 	f.read_to_string(&mut buffer)?;
 
-	if source(&buffer) {
+	let files = Rc::new(RefCell::new(SourceMap::default()));
+	let file = files.borrow_mut().add(path, &buffer);
+
+	if run(&files, file) {
 		return Err(RunError::Exec);
 	};
 
 	Ok(())
 }
 
+/// Prints Markdown documentation of the script's public API
+///
+/// # Errors
+///
+/// Will return `RunError::Io` if `path`
+/// does not exist or the user does not have permission to read it.
+//
+/// Will return `RunError::Exec` if the script can't be parsed.
+pub fn doc(path: &str) -> Result<(), RunError> {
+	let mut f = fs::File::open(path)?;
+
+	let mut buffer = String::new();
+	f.read_to_string(&mut buffer)?;
+
+	let mut files = SourceMap::default();
+	let file = files.add(path, &buffer);
+
+	let statements = parse(&files, file).ok_or(RunError::Exec)?;
+
+	print!("{}", doc::generate(path, &statements));
+
+	Ok(())
+}
+
 /// # Errors
 ///
 /// Will return `Err` if there are any errors during reading from command line.
 pub fn repl() -> Result<(), io::Error> {
+	let files = Rc::new(RefCell::new(SourceMap::default()));
+
 	loop {
 		print!(">>> ");
 		io::stdout().flush()?;
@@ -45,8 +83,10 @@ pub fn repl() -> Result<(), io::Error> {
 		// the user doesn't have to 😇
 		buffer += ";";
 
+		let file = files.borrow_mut().add("<repl>", &buffer);
+
 		// TODO: merge envs when doing REPL
-		if source(&buffer) {
+		if run(&files, file) {
 			eprintln!("Errors occurred");
 		}
 	}
@@ -62,36 +102,55 @@ pub fn repl() -> Result<(), io::Error> {
 /// returned bool indicates if any error(s) occurred
 #[must_use]
 pub fn source(source: &str) -> bool {
-	// Scanning
-	let (tokens, errors) = scanner::scan(source);
-
-	if !errors.is_empty() {
-		errors::report(source, "Scan", &errors);
-
-		return true;
-	}
+	let files = Rc::new(RefCell::new(SourceMap::default()));
+	let file = files.borrow_mut().add("<source>", source);
 
-	// Parsing
-	let (statements, errors) = parser::parse(tokens);
-
-	if !errors.is_empty() {
-		errors::report(source, "Parse", &errors);
+	run(&files, file)
+}
 
+fn run(files: &Rc<RefCell<SourceMap>>, file: FileId) -> bool {
+	let statements = if let Some(statements) = parse(&files.borrow(), file) {
+		statements
+	} else {
 		return true;
-	}
+	};
 
 	// Resolving
 	if let Err(error) = resolver::resolve(&statements) {
-		errors::report(source, "Resolve", &[error]);
+		errors::report(&files.borrow(), "Resolve", &[error]);
 
 		true
 
 	// Interpreting 😇
-	} else if let Err(error) = interpreter::interpret(&statements) {
-		errors::report(source, "Runtime", &[error]);
+	} else if let Err(error) = interpreter::interpret(&statements, files, file)
+	{
+		errors::report(&files.borrow(), "Runtime", &[error]);
 
 		true
 	} else {
 		false
 	}
 }
+
+/// Scans and parses the file, reporting errors if there are any
+fn parse(files: &SourceMap, file: FileId) -> Option<Vec<Stmt>> {
+	// Scanning
+	let (tokens, errors) = scanner::scan(&files.get(file).source, file);
+
+	if !errors.is_empty() {
+		errors::report(files, "Scan", &errors);
+
+		return None;
+	}
+
+	// Parsing
+	let (statements, errors) = parser::parse(tokens);
+
+	if !errors.is_empty() {
+		errors::report(files, "Parse", &errors);
+
+		return None;
+	}
+
+	Some(statements)
+}
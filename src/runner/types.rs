@@ -1,6 +1,7 @@
 use std::{
 	fmt,
 	io::{self},
+	rc::Rc,
 };
 
 
@@ -16,14 +17,15 @@ impl From<io::Error> for RunError {
 }
 
 pub struct Line {
-	pub number: u32,
+	pub file_name: Rc<str>,
+	pub number: usize,
 	pub offset: usize,
 	pub content: String,
 }
 
 impl Line {
 	pub fn prefix(&self) -> String {
-		format!("[{}:{}]", self.number, self.offset)
+		format!("[{}:{}:{}]", self.file_name, self.number, self.offset)
 	}
 }
 
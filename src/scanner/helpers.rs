@@ -1,19 +1,20 @@
 use super::types::{ConsumptionResult, ScanError, ScannerIter};
-use crate::token::TokenType;
+use crate::{source::FileId, token::TokenType, try_exact_convert};
 
 
 /// will consume chars while peek matches the predicate
 ///
+/// `from` should be the offset of the first char that could be consumed
+///
 /// returns a struct with the last offset (in bytes) of where
 /// the next char would be
 /// (regardless of it being there or the iterator ending)
-///
-/// sets `hit_eof` when the scanning has reached eof
 pub fn consume_while_peek(
 	chars: ScannerIter,
+	from: usize,
 	predicate: impl Fn(&char) -> bool,
 ) -> ConsumptionResult {
-	let mut last_offset = 0;
+	let mut last_offset = from;
 
 	loop {
 		break match chars.peek() {
@@ -25,40 +26,484 @@ pub fn consume_while_peek(
 				continue;
 			}
 			// char doesn't match the predicate, so we return the result
-			Some((i, _)) => ConsumptionResult {
-				last_offset: *i,
-				hit_eof: false,
-			},
+			Some((i, _)) => ConsumptionResult { last_offset: *i },
 			// we hit the eof
-			None => ConsumptionResult {
-				last_offset,
-				hit_eof: true,
-			},
+			None => ConsumptionResult { last_offset },
 		};
 	}
 }
 
+/// peeks the char after the next one
+pub fn peek_second(chars: ScannerIter) -> Option<char> {
+	chars.clone().nth(1).map(|(_, c)| c)
+}
+
+/// consumes a number literal, expects the first digit to be already consumed
+///
+/// supports `0x`, `0o` and `0b` prefixes, `_` digit separators and the
+/// scientific notation. Returns the parsed number along with the offset of
+/// where the next char would be
+pub fn consume_number(
+	chars: ScannerIter,
+	source: &str,
+	offset: usize,
+	file: FileId,
+) -> Result<(f64, usize), ScanError> {
+	let radix = match (&source[offset..=offset], chars.peek()) {
+		("0", Some((_, 'x'))) => 16,
+		("0", Some((_, 'o'))) => 8,
+		("0", Some((_, 'b'))) => 2,
+		_ => 10,
+	};
+
+	let end = if radix == 10 {
+		consume_decimal(chars, source, offset, file)?
+	} else {
+		// consume the prefix
+		chars.next();
+
+		consume_while_peek(chars, offset + 2, |c| {
+			c.is_ascii_alphanumeric() || *c == '_'
+		})
+		.last_offset
+	};
+
+	// a number cannot be glued to an identifier (e.g. `12abc`)
+	if let Some((i, c)) = chars.peek() {
+		if c.is_alphanumeric() || *c == '_' {
+			let (i, c) = (*i, *c);
+
+			let _ = consume_while_peek(chars, i, |c| {
+				c.is_alphanumeric() || *c == '_'
+			});
+
+			return Err(ScanError {
+				file,
+				offset: i,
+				message: format!("Unexpected {:?} in number literal", c),
+			});
+		}
+	}
+
+	let number = if radix == 10 {
+		parse_decimal_number(&source[offset..end], offset, file)?
+	} else {
+		parse_prefixed_number(&source[offset + 2..end], offset, radix, file)?
+	};
+
+	Ok((number, end))
+}
+
+fn parse_decimal_number(
+	literal: &str,
+	offset: usize,
+	file: FileId,
+) -> Result<f64, ScanError> {
+	let digits = literal.replace('_', "");
+
+	let number: f64 = digits.parse().map_err(|_| ScanError {
+		file,
+		offset,
+		message: "Invalid number literal".to_owned(),
+	})?;
+
+	if !number.is_finite() {
+		return Err(ScanError {
+			file,
+			offset,
+			message: "Number literal is too big".to_owned(),
+		});
+	}
+
+	// integer literals have to be exact, like the prefixed ones
+	if let Ok(integer) = digits.parse::<u128>() {
+		try_exact_convert!(integer, u128, f64).map_err(|_| ScanError {
+			file,
+			offset,
+			message: "Number literal cannot be represented exactly".to_owned(),
+		})?;
+	}
+
+	Ok(number)
+}
+
+// consumes the rest of a decimal number literal
+//
+// returns the offset of where the next char would be
+fn consume_decimal(
+	chars: ScannerIter,
+	source: &str,
+	offset: usize,
+	file: FileId,
+) -> Result<usize, ScanError> {
+	let is_digit = |c: &char| c.is_ascii_digit() || *c == '_';
+
+	// the dot is a part of the number only if it's followed by a digit, so
+	// that `1..2` or `1.` don't get swallowed
+	let dot_then_digit = |chars: ScannerIter| {
+		matches!(chars.peek(), Some((_, '.')))
+			&& peek_second(chars).is_some_and(|c| c.is_ascii_digit())
+	};
+
+	let mut end = consume_while_peek(chars, offset + 1, is_digit).last_offset;
+
+	if dot_then_digit(chars) {
+		chars.next();
+
+		end = consume_while_peek(chars, end + 1, is_digit).last_offset;
+
+		// that's the `1.2.3` case
+		if dot_then_digit(chars) {
+			let _ = consume_while_peek(chars, end, |c| {
+				c.is_ascii_digit() || *c == '_' || *c == '.'
+			});
+
+			return Err(ScanError {
+				file,
+				offset: end,
+				message: "Unexpected `.` in number literal".to_owned(),
+			});
+		}
+	}
+
+	if let Some((e_offset, 'e' | 'E')) = chars.peek() {
+		let e_offset = *e_offset;
+
+		chars.next();
+
+		let mut exponent_start = e_offset + 1;
+
+		if let Some((_, '+' | '-')) = chars.peek() {
+			chars.next();
+
+			exponent_start += 1;
+		}
+
+		if !matches!(chars.peek(), Some((_, c)) if c.is_ascii_digit()) {
+			return Err(ScanError {
+				file,
+				offset: e_offset,
+				message: "Expected digits after the exponent".to_owned(),
+			});
+		}
+
+		end = consume_while_peek(chars, exponent_start, is_digit).last_offset;
+	}
+
+	validate_separators(&source[offset..end], offset, 10, file)?;
+
+	Ok(end)
+}
+
+// parses the digits of `0x`, `0o` and `0b` literals, `offset` should point at
+// the start of the whole literal
+fn parse_prefixed_number(
+	digits: &str,
+	offset: usize,
+	radix: u32,
+	file: FileId,
+) -> Result<f64, ScanError> {
+	if digits.is_empty() {
+		return Err(ScanError {
+			file,
+			offset,
+			message: "Expected digits after the number prefix".to_owned(),
+		});
+	}
+
+	validate_separators(digits, offset + 2, radix, file)?;
+
+	if let Some((i, c)) = digits
+		.char_indices()
+		.find(|(_, c)| *c != '_' && !c.is_digit(radix))
+	{
+		return Err(ScanError {
+			file,
+			offset: offset + 2 + i,
+			message: format!("Invalid digit {:?} in base {} literal", c, radix),
+		});
+	}
+
+	let number =
+		u64::from_str_radix(&digits.replace('_', ""), radix).map_err(|_| {
+			ScanError {
+				file,
+				offset,
+				message: "Number literal is too big".to_owned(),
+			}
+		})?;
+
+	try_exact_convert!(number, u64, f64).map_err(|_| ScanError {
+		file,
+		offset,
+		message: "Number literal cannot be represented exactly".to_owned(),
+	})
+}
+
+// every `_` separator has to be followed by a digit
+fn validate_separators(
+	literal: &str,
+	offset: usize,
+	radix: u32,
+	file: FileId,
+) -> Result<(), ScanError> {
+	let mut literal_chars = literal.char_indices().peekable();
+
+	while let Some((i, c)) = literal_chars.next() {
+		if c == '_'
+			&& !matches!(literal_chars.peek(), Some((_, n)) if n.is_digit(radix))
+		{
+			return Err(ScanError {
+				file,
+				offset: offset + i,
+				message: "Digit separator `_` must be followed by a digit"
+					.to_owned(),
+			});
+		}
+	}
+
+	Ok(())
+}
+
 pub fn expect_char(
 	chars: ScannerIter,
 	after: char,
 	offset: usize,
 	override_message: Option<&str>,
+	file: FileId,
 ) -> Result<char, ScanError> {
 	if let Some(c) = chars.next() {
 		Ok(c.1)
 	} else if let Some(msg) = override_message {
 		Err(ScanError {
+			file,
 			message: msg.to_owned(),
 			offset,
 		})
 	} else {
 		Err(ScanError {
+			file,
 			message: format!("Expected char after `{}`", after),
 			offset,
 		})
 	}
 }
 
+/// consumes a (possibly nested) block comment
+///
+/// expects the opening `/*` to be already consumed, `offset` should point at
+/// it. Returns the offset of where the next char would be. If the comment is
+/// left unterminated, the error points at the opening delimiter that's missing
+/// its closer
+pub fn consume_block_comment(
+	chars: ScannerIter,
+	offset: usize,
+	file: FileId,
+) -> Result<usize, ScanError> {
+	// byte offsets of every `/*` that is still waiting for its `*/`
+	let mut openers = vec![offset];
+
+	while let Some((i, c)) = chars.next() {
+		match (c, chars.peek()) {
+			('/', Some((_, '*'))) => {
+				chars.next();
+
+				openers.push(i);
+			}
+			('*', Some((_, '/'))) => {
+				chars.next();
+
+				openers.pop();
+
+				if openers.is_empty() {
+					return Ok(i + 2);
+				}
+			}
+			_ => (),
+		}
+	}
+
+	Err(ScanError {
+		file,
+		offset: openers.pop().unwrap_or(offset),
+		message: "Unterminated block comment".to_owned(),
+	})
+}
+
+/// consumes a raw string literal (`"""..."""`)
+///
+/// expects the opening `"""` to be already consumed, `offset` should point
+/// at it. Returns the literal's contents with the common indentation
+/// stripped, along with the offset of where the next char would be
+pub fn consume_raw_string(
+	chars: ScannerIter,
+	offset: usize,
+	file: FileId,
+) -> Result<(String, usize), ScanError> {
+	let mut raw = String::new();
+
+	while let Some((i, c)) = chars.next() {
+		if c == '"'
+			&& matches!(chars.peek(), Some((_, '"')))
+			&& peek_second(chars) == Some('"')
+		{
+			chars.next();
+			chars.next();
+
+			return Ok((strip_indentation(&raw), i + 3));
+		}
+
+		raw.push(c);
+	}
+
+	Err(ScanError {
+		file,
+		offset,
+		message: "Unterminated raw string literal".to_owned(),
+	})
+}
+
+// strips the indentation common to every non-blank line, so that raw strings
+// can be indented along with the code around them
+//
+// the newline right after the opening `"""` and the whitespace before the
+// closing one are dropped as well
+fn strip_indentation(raw: &str) -> String {
+	// single line literals are kept as they are
+	if !raw.contains('\n') {
+		return raw.to_owned();
+	}
+
+	let raw = raw.replace("\r\n", "\n");
+
+	let raw = raw.strip_prefix('\n').unwrap_or(&raw);
+
+	let raw = match raw.rsplit_once('\n') {
+		Some((rest, last_line)) if last_line.trim().is_empty() => rest,
+		_ => raw,
+	};
+
+	let indentation = raw
+		.split('\n')
+		.filter(|line| !line.trim().is_empty())
+		.map(|line| &line[..line.len() - line.trim_start().len()])
+		.reduce(|common, indentation| {
+			let common_len = common
+				.char_indices()
+				.zip(indentation.chars())
+				.find(|((_, c1), c2)| c1 != c2)
+				.map_or_else(
+					|| common.len().min(indentation.len()),
+					|((i, _), _)| i,
+				);
+
+			&common[..common_len]
+		})
+		.unwrap_or("");
+
+	raw.split('\n')
+		.map(|line| {
+			line.strip_prefix(indentation)
+				.unwrap_or_else(|| line.trim_start())
+		})
+		.collect::<Vec<&str>>()
+		.join("\n")
+}
+
+/// consumes an escape sequence and returns the char it represents
+///
+/// expects the `\` to be already consumed, `offset` should point at it,
+/// so that the errors can point at the exact bad escape
+pub fn consume_escape(
+	chars: ScannerIter,
+	offset: usize,
+	file: FileId,
+) -> Result<char, ScanError> {
+	let escaped = expect_char(
+		chars,
+		'\\',
+		offset,
+		Some("Expected escape sequence after `\\`"),
+		file,
+	)?;
+
+	match escaped {
+		'n' => Ok('\n'),
+		't' => Ok('\t'),
+		'r' => Ok('\r'),
+		'0' => Ok('\0'),
+		'\\' => Ok('\\'),
+		'"' => Ok('"'),
+		'\'' => Ok('\''),
+		'$' => Ok('$'),
+		'u' => consume_unicode_escape(chars, offset, file),
+		c => Err(ScanError {
+			file,
+			offset,
+			message: format!("Unknown escape sequence `\\{}`", c),
+		}),
+	}
+}
+
+// consumes the `{1F600}` part of an `\u{1F600}` escape
+//
+// uses peek everywhere, so that a malformed escape doesn't eat the closing
+// quote of the literal it's in
+fn consume_unicode_escape(
+	chars: ScannerIter,
+	offset: usize,
+	file: FileId,
+) -> Result<char, ScanError> {
+	if let Some((_, '{')) = chars.peek() {
+		chars.next();
+	} else {
+		return Err(ScanError {
+			file,
+			offset,
+			message: "Expected `{` after `\\u`".to_owned(),
+		});
+	}
+
+	let mut digits = String::new();
+
+	while let Some((_, c)) = chars.peek() {
+		if !c.is_ascii_hexdigit() {
+			break;
+		}
+
+		digits.push(*c);
+		chars.next();
+	}
+
+	if let Some((_, '}')) = chars.peek() {
+		chars.next();
+	} else {
+		return Err(ScanError {
+			file,
+			offset,
+			message: "Expected `}` to close the unicode escape".to_owned(),
+		});
+	}
+
+	if digits.is_empty() || digits.len() > 6 {
+		return Err(ScanError {
+			file,
+			offset,
+			message: "Unicode escape must have between 1 and 6 hex digits"
+				.to_owned(),
+		});
+	}
+
+	u32::from_str_radix(&digits, 16)
+		.ok()
+		.and_then(char::from_u32)
+		.ok_or_else(|| ScanError {
+			file,
+			offset,
+			message: format!("`{}` is not a valid unicode code point", digits),
+		})
+}
+
 pub fn tokenize_identifier(identifier: &str) -> TokenType {
 	match identifier {
 		"and" => TokenType::And,
@@ -80,6 +525,16 @@ pub fn tokenize_identifier(identifier: &str) -> TokenType {
 		"break" => TokenType::Break,
 		"continue" => TokenType::Continue,
 		"extends" => TokenType::Extends,
+		"in" => TokenType::In,
+		"match" => TokenType::Match,
+		"throw" => TokenType::Throw,
+		"try" => TokenType::Try,
+		"catch" => TokenType::Catch,
+		"finally" => TokenType::Finally,
+		"import" => TokenType::Import,
+		"export" => TokenType::Export,
+		"as" => TokenType::As,
+		"enum" => TokenType::Enum,
 		_ => TokenType::Identifier(identifier.into()),
 	}
 }
@@ -2,4 +2,5 @@ mod helpers;
 mod scan;
 mod types;
 
-pub use scan::scan;
+pub use scan::{scan, scan_lossless};
+pub use types::ScanError;
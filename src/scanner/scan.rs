@@ -1,33 +1,89 @@
 use super::{
-	helpers::{consume_while_peek, expect_char, tokenize_identifier},
+	helpers::{
+		consume_block_comment,
+		consume_escape,
+		consume_number,
+		consume_raw_string,
+		consume_while_peek,
+		expect_char,
+		peek_second,
+		tokenize_identifier,
+	},
 	types::{ScanError, ScannerIter},
 };
-use crate::token::{self, Location, TokenType};
+use crate::{
+	source::FileId,
+	token::{self, Location, TemplatePart, TokenType, Trivia, TriviaKind},
+};
+
+use std::{mem, rc::Rc};
+
+
+pub fn scan(source: &str, file: FileId) -> (Vec<token::Token>, Vec<ScanError>) {
+	let (tokens, _, errors) = scan_with_trivia(source, file, false);
+
+	(tokens, errors)
+}
 
+/// Scans the source without throwing away whitespace and comments - they're
+/// attached to the tokens following them as `leading_trivia`.
+///
+/// Together with the trailing trivia (the one after the last token), every
+/// byte of a correct source is covered by either a token or trivia, so the
+/// source can be reproduced exactly. Tokens inside template strings don't
+/// carry trivia, as they're covered by the template token itself.
+#[must_use]
+pub fn scan_lossless(
+	source: &str,
+	file: FileId,
+) -> (Vec<token::Token>, Vec<Trivia>, Vec<ScanError>) {
+	scan_with_trivia(source, file, true)
+}
 
-pub fn scan(source: &str) -> (Vec<token::Token>, Vec<ScanError>) {
+fn scan_with_trivia(
+	source: &str,
+	file: FileId,
+	keep_trivia: bool,
+) -> (Vec<token::Token>, Vec<Trivia>, Vec<ScanError>) {
 	let mut tokens = vec![];
 	let mut errors = vec![];
+	let mut trivia = vec![];
 
 	let mut chars = source.char_indices().peekable();
 
 	while let Some(_peek) = chars.peek() {
 		// We should be at the beginning of the next lexeme
-		match scan_token(&mut chars, source) {
-			Ok(Some(token)) => tokens.push(token),
+		match scan_token(&mut chars, source, &mut trivia, file) {
+			Ok(Some(mut token)) => {
+				if keep_trivia {
+					token.leading_trivia = mem::take(&mut trivia);
+				} else {
+					// doc comments are needed by the parser
+					token.leading_trivia = mem::take(&mut trivia)
+						.into_iter()
+						.filter(|t| matches!(t.kind, TriviaKind::DocComment(_)))
+						.collect();
+				}
+
+				tokens.push(token);
+			}
 			Ok(None) => break, // iterator is exhausted
 			Err(err) => errors.push(err),
 		}
 	}
 
-	(tokens, errors)
+	(tokens, trivia, errors)
 }
 
 /// Consumes the next token's chars
+///
+/// skipped whitespace and comments are pushed onto `trivia`
 #[allow(clippy::too_many_lines)]
 fn scan_token(
 	chars: ScannerIter,
 	source: &str,
+	trivia: &mut Vec<Trivia>,
+	file: FileId,
 ) -> Result<Option<token::Token>, ScanError> {
 	// using while, because we want to skip unimportant chars, like whitespace
 	while let Some((i, c)) = chars.next() {
@@ -42,13 +98,122 @@ fn scan_token(
 			'[' => TokenType::LeftSquareBracket,
 			']' => TokenType::RightSquareBracket,
 			',' => TokenType::Comma,
-			'.' => TokenType::Dot,
-			'-' => TokenType::Minus,
-			'+' => TokenType::Plus,
-			'%' => TokenType::Modulo,
+			'.' => {
+				if let Some((_, '.')) = chars.peek() {
+					chars.next();
+
+					token_len += 1;
+
+					match chars.peek() {
+						Some((_, '.')) => {
+							chars.next();
+
+							token_len += 1;
+
+							TokenType::DotDotDot
+						}
+						Some((_, '=')) => {
+							chars.next();
+
+							token_len += 1;
+
+							TokenType::DotDotEqual
+						}
+						_ => TokenType::DotDot,
+					}
+				} else {
+					TokenType::Dot
+				}
+			}
 			';' => TokenType::Semicolon,
 			':' => TokenType::Colon,
-			'*' => TokenType::Star,
+			'-' => {
+				if let Some((_, '=')) = chars.peek() {
+					chars.next();
+
+					token_len += 1;
+
+					TokenType::MinusEqual
+				} else {
+					TokenType::Minus
+				}
+			}
+			'+' => {
+				if let Some((_, '=')) = chars.peek() {
+					chars.next();
+
+					token_len += 1;
+
+					TokenType::PlusEqual
+				} else {
+					TokenType::Plus
+				}
+			}
+			'%' => {
+				if let Some((_, '=')) = chars.peek() {
+					chars.next();
+
+					token_len += 1;
+
+					TokenType::ModuloEqual
+				} else {
+					TokenType::Modulo
+				}
+			}
+			'*' => match chars.peek() {
+				Some((_, '=')) => {
+					chars.next();
+
+					token_len += 1;
+
+					TokenType::StarEqual
+				}
+				Some((_, '*')) => {
+					chars.next();
+
+					token_len += 1;
+
+					TokenType::StarStar
+				}
+				_ => TokenType::Star,
+			},
+			// `//` starts a comment, so integer division is spelled `~/`
+			'~' => {
+				if let Some((_, '/')) = chars.peek() {
+					chars.next();
+
+					token_len += 1;
+
+					TokenType::TildeSlash
+				} else {
+					TokenType::Tilde
+				}
+			}
+			'?' => {
+				let token_type = match chars.peek() {
+					Some((_, '.')) => TokenType::QuestionDot,
+					Some((_, '[')) => TokenType::QuestionLeftSquareBracket,
+					Some((_, '?')) => TokenType::QuestionQuestion,
+					_ => {
+						return Err(ScanError {
+							file,
+							offset: i,
+							message: "Unexpected character '?', did you mean \
+							          `?.`, `?[` or `??`?"
+								.into(),
+						});
+					}
+				};
+
+				chars.next();
+
+				token_len += 1;
+
+				token_type
+			}
+			'&' => TokenType::Ampersand,
+			'|' => TokenType::Pipe,
+			'^' => TokenType::Caret,
 			'!' => {
 				if let Some((_, '=')) = chars.peek() {
 					chars.next();
@@ -67,101 +232,255 @@ fn scan_token(
 					token_len += 1;
 
 					TokenType::EqualEqual
+				} else if let Some((_, '>')) = chars.peek() {
+					chars.next();
+
+					token_len += 1;
+
+					TokenType::FatArrow
 				} else {
 					TokenType::Equal
 				}
 			}
-			'<' => {
-				if let Some((_, '=')) = chars.peek() {
+			'<' => match chars.peek() {
+				Some((_, '=')) => {
 					chars.next();
 
 					token_len += 1;
 
 					TokenType::LessEqual
-				} else {
-					TokenType::Less
 				}
-			}
-			'>' => {
-				if let Some((_, '=')) = chars.peek() {
+				Some((_, '<')) => {
+					chars.next();
+
+					token_len += 1;
+
+					TokenType::LessLess
+				}
+				_ => TokenType::Less,
+			},
+			'>' => match chars.peek() {
+				Some((_, '=')) => {
 					chars.next();
 
 					token_len += 1;
 
 					TokenType::GreaterEqual
-				} else {
-					TokenType::Greater
 				}
-			}
+				Some((_, '>')) => {
+					chars.next();
+
+					token_len += 1;
+
+					TokenType::GreaterGreater
+				}
+				_ => TokenType::Greater,
+			},
 			'/' => {
 				if let Some((_, '/')) = chars.peek() {
 					// comment goes until the end of the line
-					chars.take_while(|(_, c)| *c != '\n').for_each(drop);
+					let comment_end =
+						consume_while_peek(chars, i + 1, |c| *c != '\n')
+							.last_offset;
+
+					let comment = &source[i..comment_end];
+
+					// `////` and longer are just regular comments
+					let kind = if comment.starts_with("///")
+						&& !comment.starts_with("////")
+					{
+						let text = &comment[3..];
+
+						TriviaKind::DocComment(
+							text.strip_prefix(' ')
+								.unwrap_or(text)
+								.trim_end_matches('\r')
+								.into(),
+						)
+					} else {
+						TriviaKind::LineComment
+					};
+
+					push_trivia(trivia, kind, file, i, comment_end);
+
+					continue;
+				} else if let Some((_, '*')) = chars.peek() {
+					chars.next();
+
+					let comment_end = consume_block_comment(chars, i, file)?;
+
+					push_trivia(
+						trivia,
+						TriviaKind::BlockComment,
+						file,
+						i,
+						comment_end,
+					);
 
 					continue;
+				} else if let Some((_, '=')) = chars.peek() {
+					chars.next();
+
+					token_len += 1;
+
+					TokenType::SlashEqual
+				} else {
+					TokenType::Slash
 				}
+			}
+			'"' if matches!(chars.peek(), Some((_, '"')))
+				&& peek_second(chars) == Some('"') =>
+			{
+				chars.next();
+				chars.next();
+
+				let (value, end) = consume_raw_string(chars, i, file)?;
 
-				TokenType::Slash
+				token_len = end - i;
+
+				TokenType::String(value.into())
 			}
 			'"' => {
-				let res = consume_while_peek(chars, |c| *c != '"');
+				let mut value = String::new();
+				let mut parts = Vec::new();
 
-				if res.hit_eof {
-					return Err(ScanError {
-						offset: i,
-						message: "Unterminated string literal".to_owned(),
-					});
+				// we report only the first error, but we still want to
+				// consume the whole literal before doing so
+				let mut literal_error = None;
+
+				loop {
+					match chars.next() {
+						Some((j, '"')) => {
+							token_len = j + 1 - i;
+
+							break;
+						}
+						Some((j, '$'))
+							if matches!(chars.peek(), Some((_, '{'))) =>
+						{
+							chars.next();
+
+							if !value.is_empty() {
+								parts.push(TemplatePart::Literal(
+									mem::take(&mut value).into(),
+								));
+							}
+
+							match consume_interpolation(chars, source, j, file)
+							{
+								Ok(tokens) => {
+									parts
+										.push(TemplatePart::Expression(tokens));
+								}
+								Err(err) => {
+									literal_error.get_or_insert(err);
+								}
+							}
+						}
+						Some((j, '\\')) => match consume_escape(chars, j, file)
+						{
+							Ok(c) => value.push(c),
+							Err(err) => {
+								literal_error.get_or_insert(err);
+							}
+						},
+						Some((_, c)) => value.push(c),
+						None => {
+							return Err(ScanError {
+								file,
+								offset: i,
+								message: "Unterminated string literal"
+									.to_owned(),
+							});
+						}
+					}
 				}
 
-				chars.next();
+				if let Some(err) = literal_error {
+					return Err(err);
+				}
 
-				TokenType::String(source[i + 1..res.last_offset].into())
+				if parts.is_empty() {
+					TokenType::String(value.into())
+				} else {
+					if !value.is_empty() {
+						parts.push(TemplatePart::Literal(value.into()));
+					}
+
+					TokenType::Template(Rc::new(parts))
+				}
 			}
 			'\'' => {
-				let c = expect_char(chars, '\'', i, None)?;
+				let c = match expect_char(chars, '\'', i, None, file)? {
+					'\\' => consume_escape(chars, i + 1, file).inspect_err(
+						|_| {
+							// get rid of remaining chars
+							let _ =
+								consume_while_peek(chars, i, |c| *c != '\'');
+							chars.next();
+						},
+					)?,
+					c => c,
+				};
 
-				let closer = expect_char(chars, c, i, None)?;
+				let closer = expect_char(chars, c, i, None, file)?;
 
 				if '\'' == closer {
+					token_len =
+						chars.peek().map_or(source.len(), |(end, _)| *end) - i;
+
 					TokenType::Char(c)
 				} else {
 					// get rid of remaining chars
-					let _ = consume_while_peek(chars, |c| *c != '\'');
+					let _ = consume_while_peek(chars, i, |c| *c != '\'');
 					chars.next();
 
 					return Err(ScanError {
+						file,
 						offset: i,
 						message: "Expected closing ' after char".to_owned(),
 					});
 				}
 			}
 			c if c.is_ascii_digit() => {
-				let number_end = consume_while_peek(chars, |c| {
-					*c == '.' || c.is_ascii_digit()
-				})
-				.last_offset;
-
-				let to_parse = &source[i..number_end];
+				let (number, number_end) =
+					consume_number(chars, source, i, file)?;
 
 				token_len = number_end - i;
 
-				TokenType::Number(to_parse.parse().expect("Parsed number"))
+				TokenType::Number(number)
 			}
 			c if c.is_alphabetic() || c == '_' => {
-				let identifier_end = consume_while_peek(chars, |peek| {
-					peek.is_alphanumeric() || *peek == '_'
-				})
-				.last_offset;
+				let identifier_end =
+					consume_while_peek(chars, i + token_len, |peek| {
+						peek.is_alphanumeric() || *peek == '_'
+					})
+					.last_offset;
 
 				token_len = identifier_end - i;
 
 				tokenize_identifier(&source[i..identifier_end])
 			}
 			c if c.is_whitespace() => {
+				let whitespace_end =
+					consume_while_peek(chars, i + token_len, |c| {
+						c.is_whitespace()
+					})
+					.last_offset;
+
+				push_trivia(
+					trivia,
+					TriviaKind::Whitespace,
+					file,
+					i,
+					whitespace_end,
+				);
+
 				continue;
 			}
 			_ => {
 				return Err(ScanError {
+					file,
 					offset: i,
 					message: format!("Unexpected character {:?}", c),
 				});
@@ -171,11 +490,96 @@ fn scan_token(
 		return Ok(Some(token::Token {
 			token_type,
 			location: Location {
+				file,
 				byte_offset: i,
 				byte_length: token_len,
 			},
+			leading_trivia: Vec::new(),
 		}));
 	}
 
 	Ok(None)
 }
+
+#[inline]
+fn push_trivia(
+	trivia: &mut Vec<Trivia>,
+	kind: TriviaKind,
+	file: FileId,
+	from: usize,
+	to: usize,
+) {
+	trivia.push(Trivia {
+		kind,
+		location: Location {
+			file,
+			byte_offset: from,
+			byte_length: to - from,
+		},
+	});
+}
+
+/// Consumes the tokens of an interpolated expression in a template string
+///
+/// expects the `${` to be already consumed, `offset` should point at it.
+/// Returned tokens include the closing `}`, so that the parser has something
+/// to blame when the expression ends abruptly
+fn consume_interpolation(
+	chars: ScannerIter,
+	source: &str,
+	offset: usize,
+	file: FileId,
+) -> Result<Vec<token::Token>, ScanError> {
+	let mut tokens = Vec::new();
+	let mut first_error = None;
+
+	// trivia inside the interpolation is covered by the template token
+	let mut trivia = Vec::new();
+
+	// how many `{` are waiting for their `}` inside the interpolation
+	let mut depth = 0_usize;
+
+	loop {
+		let token = match scan_token(chars, source, &mut trivia, file) {
+			Ok(Some(token)) => token,
+			Ok(None) => {
+				return Err(ScanError {
+					file,
+					offset,
+					message: "Unterminated string interpolation".to_owned(),
+				});
+			}
+			Err(err) => {
+				first_error.get_or_insert(err);
+
+				continue;
+			}
+		};
+
+		match token.token_type {
+			TokenType::LeftBrace => depth += 1,
+			TokenType::RightBrace if depth == 0 => {
+				if tokens.is_empty() {
+					first_error.get_or_insert_with(|| ScanError {
+						file,
+						offset,
+						message: "Expected an expression inside `${}`"
+							.to_owned(),
+					});
+				}
+
+				tokens.push(token);
+
+				break;
+			}
+			TokenType::RightBrace => depth -= 1,
+			_ => (),
+		}
+
+		trivia.clear();
+
+		tokens.push(token);
+	}
+
+	first_error.map_or(Ok(tokens), Err)
+}
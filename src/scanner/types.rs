@@ -1,9 +1,10 @@
-use crate::{runner::DescribableError, token::Location};
+use crate::{runner::DescribableError, source::FileId, token::Location};
 
 use std::{iter, str};
 
 
 pub struct ScanError {
+	pub file: FileId,
 	pub offset: usize,
 	pub message: String,
 }
@@ -11,6 +12,7 @@ pub struct ScanError {
 impl DescribableError for ScanError {
 	fn location(&self) -> Location {
 		Location {
+			file: self.file,
 			byte_offset: self.offset,
 			byte_length: 1,
 		}
@@ -25,5 +27,4 @@ pub type ScannerIter<'a, 'b> = &'a mut iter::Peekable<str::CharIndices<'b>>;
 
 pub struct ConsumptionResult {
 	pub last_offset: usize,
-	pub hit_eof: bool,
 }
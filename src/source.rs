@@ -0,0 +1,77 @@
+use std::{iter, rc::Rc};
+
+
+/// Identifies a file registered in a `SourceMap`
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub struct FileId(pub usize);
+
+pub struct SourceFile {
+	pub name: Rc<str>,
+	pub source: Rc<str>,
+	// byte offsets at which every line starts, so that we don't have to rescan
+	// the whole source for every reported error
+	line_starts: Vec<usize>,
+}
+
+/// A line of a source file, found by `SourceFile::line`
+pub struct LineSpan {
+	/// starts at 1
+	pub number: usize,
+	pub start: usize,
+	pub end: usize,
+}
+
+impl SourceFile {
+	#[must_use]
+	pub fn new(name: &str, source: &str) -> Self {
+		let line_starts = iter::once(0)
+			.chain(source.match_indices('\n').map(|(i, _)| i + 1))
+			.collect();
+
+		Self {
+			name: Rc::from(name),
+			source: Rc::from(source),
+			line_starts,
+		}
+	}
+
+	/// Finds the line that contains `byte_offset`
+	///
+	/// offsets past the end of the source land on the last line
+	#[must_use]
+	pub fn line(&self, byte_offset: usize) -> LineSpan {
+		// line_starts always contains at least the 0, so this won't underflow
+		let index = self.line_starts.partition_point(|s| *s <= byte_offset) - 1;
+
+		let start = self.line_starts[index];
+
+		let end = self
+			.line_starts
+			.get(index + 1)
+			.map_or(self.source.len(), |next_start| next_start - 1);
+
+		LineSpan {
+			number: index + 1,
+			start,
+			end,
+		}
+	}
+}
+
+#[derive(Default)]
+pub struct SourceMap {
+	files: Vec<SourceFile>,
+}
+
+impl SourceMap {
+	pub fn add(&mut self, name: &str, source: &str) -> FileId {
+		self.files.push(SourceFile::new(name, source));
+
+		FileId(self.files.len() - 1)
+	}
+
+	#[must_use]
+	pub fn get(&self, file: FileId) -> &SourceFile {
+		&self.files[file.0]
+	}
+}
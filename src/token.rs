@@ -1,3 +1,5 @@
+use crate::source::FileId;
+
 use std::{fmt, mem, rc::Rc};
 
 
@@ -20,6 +22,10 @@ pub enum TokenType {
 	LeftSquareBracket,
 	RightSquareBracket,
 	Modulo,
+	Ampersand,
+	Pipe,
+	Caret,
+	Tilde,
 
 	// One Or Two Character Tokens
 	Bang,
@@ -30,10 +36,27 @@ pub enum TokenType {
 	GreaterEqual,
 	Less,
 	LessEqual,
+	PlusEqual,
+	MinusEqual,
+	StarEqual,
+	SlashEqual,
+	ModuloEqual,
+	FatArrow,
+	DotDot,
+	DotDotEqual,
+	DotDotDot,
+	StarStar,
+	TildeSlash,
+	QuestionDot,
+	QuestionLeftSquareBracket,
+	QuestionQuestion,
+	LessLess,
+	GreaterGreater,
 
 	// Literals
 	Identifier(Rc<str>),
 	String(Rc<str>),
+	Template(Rc<Vec<TemplatePart>>),
 	Number(f64),
 	Char(char),
 
@@ -57,9 +80,20 @@ pub enum TokenType {
 	Break,
 	Continue,
 	Extends,
+	In,
+	Match,
+	Throw,
+	Try,
+	Catch,
+	Finally,
+	Import,
+	Export,
+	As,
+	Enum,
 }
 
 impl TokenType {
+	#[must_use]
 	pub fn repr(&self) -> String {
 		match self {
 			TokenType::String(s) => format!("{:?}", s),
@@ -70,9 +104,11 @@ impl TokenType {
 		}
 	}
 
+	#[must_use]
 	pub const fn human_type(&self) -> &str {
 		match self {
 			TokenType::String(_) => "string",
+			TokenType::Template(_) => "template string",
 			TokenType::Identifier(_) => "identifier",
 			TokenType::Number(_) => "number",
 			TokenType::Char(_) => "char",
@@ -98,6 +134,26 @@ impl TokenType {
 			TokenType::GreaterEqual => ">=",
 			TokenType::Less => "<",
 			TokenType::LessEqual => "<=",
+			TokenType::PlusEqual => "+=",
+			TokenType::MinusEqual => "-=",
+			TokenType::StarEqual => "*=",
+			TokenType::SlashEqual => "/=",
+			TokenType::ModuloEqual => "%=",
+			TokenType::FatArrow => "=>",
+			TokenType::DotDot => "..",
+			TokenType::DotDotEqual => "..=",
+			TokenType::DotDotDot => "...",
+			TokenType::StarStar => "**",
+			TokenType::TildeSlash => "~/",
+			TokenType::QuestionDot => "?.",
+			TokenType::QuestionLeftSquareBracket => "?[",
+			TokenType::QuestionQuestion => "??",
+			TokenType::LessLess => "<<",
+			TokenType::GreaterGreater => ">>",
+			TokenType::Ampersand => "&",
+			TokenType::Pipe => "|",
+			TokenType::Caret => "^",
+			TokenType::Tilde => "~",
 			TokenType::And => "and",
 			TokenType::Class => "class",
 			TokenType::Else => "else",
@@ -117,19 +173,79 @@ impl TokenType {
 			TokenType::Break => "break",
 			TokenType::Continue => "continue",
 			TokenType::Extends => "extends",
+			TokenType::In => "in",
+			TokenType::Match => "match",
+			TokenType::Throw => "throw",
+			TokenType::Try => "try",
+			TokenType::Catch => "catch",
+			TokenType::Finally => "finally",
+			TokenType::Import => "import",
+			TokenType::Export => "export",
+			TokenType::As => "as",
+			TokenType::Enum => "enum",
 			TokenType::Modulo => "%",
 		}
 	}
 }
 
-#[derive(Clone)]
+/// A part of a template string (e.g. `"hi ${name}!"`)
+#[derive(Clone, Debug)]
+pub enum TemplatePart {
+	Literal(Rc<str>),
+	/// tokens of the interpolated expression, including the closing `}`
+	Expression(Vec<Token>),
+}
+
+#[derive(Clone, Debug)]
+pub enum TriviaKind {
+	Whitespace,
+	LineComment,
+	BlockComment,
+	/// `///` comment, holding its text without the slashes
+	DocComment(Rc<str>),
+}
+
+/// A piece of source that doesn't matter to the parser, but is needed to
+/// reproduce the source exactly
+#[derive(Clone, Debug)]
+pub struct Trivia {
+	pub kind: TriviaKind,
+	pub location: Location,
+}
+
+#[derive(Clone, Debug)]
 pub struct Token {
 	pub location: Location,
 	pub token_type: TokenType,
+	/// whitespace and comments preceding the token, filled only when
+	/// scanning with `scan_lossless` - otherwise only doc comments are kept
+	pub leading_trivia: Vec<Trivia>,
+}
+
+impl Token {
+	/// Joins the doc comments preceding the token into a single text
+	#[must_use]
+	pub fn doc_comment(&self) -> Option<Rc<str>> {
+		let lines: Vec<&str> = self
+			.leading_trivia
+			.iter()
+			.filter_map(|trivia| match &trivia.kind {
+				TriviaKind::DocComment(text) => Some(&**text),
+				_ => None,
+			})
+			.collect();
+
+		if lines.is_empty() {
+			None
+		} else {
+			Some(lines.join("\n").into())
+		}
+	}
 }
 
-#[derive(Clone, Copy)]
+#[derive(Clone, Copy, Debug)]
 pub struct Location {
+	pub file: FileId,
 	pub byte_offset: usize,
 	pub byte_length: usize,
 }
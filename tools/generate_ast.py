@@ -104,27 +104,51 @@ def gen_expr() -> str:
 		"""
 			Function ->
 				keyword: Token, name: Option<Token>,
-				params: Option<Rc<Vec<Token>>>, body: Option<Rc<Vec<Stmt>>>
+				params: Option<Rc<Vec<Parameter>>>, body: Option<Rc<Vec<Stmt>>>,
+				doc: Option<Rc<str>>
 		""",
 		"""
 			Set ->
 				setee: Box<Expr>, key: GetAccessor,
-				value: Box<Expr>, blame: Token
+				value: Box<Expr>, blame: Token, operator: Option<Token>
 		""",
 		"""
 			Super ->
 				blame: Token, accessor: SuperAccessor,
 				env_distance: Cell<u32>
 		""",
-		'Call -> calee: Box<Expr>, closing_paren: Token, arguments: Vec<Expr>',
-		'Assignment -> name: Token, value: Box<Expr>, env_distance: Cell<u32>',
+		"""
+			Call ->
+				calee: Box<Expr>, closing_paren: Token, arguments: Vec<Expr>,
+				optional: bool
+		""",
+		"""
+			Assignment ->
+				name: Token, value: Box<Expr>, env_distance: Cell<u32>,
+				operator: Option<Token>
+		""",
+		"""
+			DestructuringAssignment ->
+				pattern: Pattern, value: Box<Expr>, targets: Vec<Expr>,
+				blame: Token
+		""",
 		'Binary -> left: Box<Expr>, operator: Token, right: Box<Expr>',
-		'Get -> getee: Box<Expr>, key: GetAccessor, blame: Token',
+		'Get -> getee: Box<Expr>, key: GetAccessor, blame: Token, optional: bool',
 		'Identifier -> name: Token, env_distance: Cell<u32>',
 		'Object -> blame: Token, properties: Vec<Property>',
 		'This -> blame: Token, env_distance: Cell<u32>',
 		'Unary -> operator: Token, right: Box<Expr>',
 		'Grouping -> expression: Box<Expr>',
+		'Template -> parts: Vec<Expr>',
+		"""
+			Conditional ->
+				condition: Box<Expr>, then: Box<Expr>,
+				otherwise: Option<Box<Expr>>
+		""",
+		'Sequence -> statements: Vec<Stmt>, value: Option<Box<Expr>>',
+		'Match -> keyword: Token, value: Box<Expr>, arms: Vec<MatchArm>',
+		'Spread -> blame: Token, value: Box<Expr>',
+		'OptionalChain -> expression: Box<Expr>',
 		'Literal(LiteralValue)',
 	]
 
@@ -149,12 +173,55 @@ def gen_expr() -> str:
 			DotEval(Box<Expr>),
 			SubscriptionNumber(f64),
 			SubscriptionEval(Box<Expr>),
+			/// `[start:end:step]`, every part being optional
+			Slice {
+				start: Option<Box<Expr>>,
+				end: Option<Box<Expr>>,
+				step: Option<Box<Expr>>,
+			},
 		}
 
 		pub enum SuperAccessor {
 			Method(Token),
 			Call(Vec<Expr>),
 		}
+
+		pub struct Parameter {
+			pub name: Token,
+			pub default: Option<Expr>,
+			pub rest: bool,
+		}
+
+		pub struct MatchArm {
+			pub pattern: Pattern,
+			pub guard: Option<Expr>,
+			pub body: Expr,
+		}
+
+		pub enum Pattern {
+			Literal(Box<Expr>),
+			Binding(Option<Token>),
+			Type { kind: PatternType, binding: Option<Token> },
+			List {
+				before: Vec<Pattern>,
+				rest: Option<Box<Pattern>>,
+				after: Vec<Pattern>,
+			},
+			Object {
+				class: Option<Box<Expr>>,
+				properties: Vec<PropertyPattern>,
+			},
+		}
+
+		pub enum PatternType {
+			Builtin(Rc<str>),
+			Class(Box<Expr>),
+		}
+
+		pub struct PropertyPattern {
+			pub key: Rc<str>,
+			pub pattern: Pattern,
+		}
 	"""
 
 	return generate_ast(
@@ -172,7 +239,12 @@ def gen_stmt() -> str:
 		"""
 			For ->
 				condition: Option<Expr>, body: Box<Stmt>,
-				closer: Option<Box<Stmt>>
+				closer: Option<Box<Stmt>>, label: Option<Token>
+		""",
+		"""
+			ForIn ->
+				key: Option<Token>, value: Token, iterable: Expr,
+				body: Option<Box<Stmt>>, blame: Token, label: Option<Token>
 		""",
 		"""
 			If ->
@@ -182,35 +254,71 @@ def gen_stmt() -> str:
 		"""
 			Declaration ->
 				name: Token, initializer: Option<Expr>,
-				mutable: bool
+				mutable: bool, doc: Option<Rc<str>>
+		""",
+		"""
+			Destructure ->
+				pattern: Pattern, initializer: Expr,
+				mutable: bool, blame: Token, doc: Option<Rc<str>>
 		""",
 		"""
 			Class ->
-				name: Token, methods: Vec<Expr>,
-				superclass: Option<Expr>
+				name: Token, methods: Vec<Expr>, static_methods: Vec<Expr>,
+				getters: Vec<Expr>, setters: Vec<Expr>, fields: Rc<Vec<Field>>,
+				superclass: Option<Expr>, doc: Option<Rc<str>>
+		""",
+		'Enum -> name: Token, variants: Vec<Variant>, doc: Option<Rc<str>>',
+		"""
+			Try ->
+				body: Option<Box<Stmt>>, catch: Option<Catch>,
+				finally: Option<Box<Stmt>>
 		""",
 		'Return -> keyword: Token, expression: Option<Expr>',
+		'Throw -> keyword: Token, expression: Expr',
+		'Import -> path: Rc<str>, name: Token, blame: Token',
+		'Export -> declaration: Box<Stmt>, names: Vec<Token>, keyword: Token',
 		'Expression -> expression: Expr',
 		'Block -> statements: Vec<Stmt>',
-		'Continue -> keyword: Token',
+		'Continue -> keyword: Token, label: Option<Token>',
 		'Print -> expression: Expr',
-		'Break -> keyword: Token',
+		'Break -> keyword: Token, label: Option<Token>',
 	]
 
 	imports = [
 		'crate::token::Token',
-		'crate::ast::expr::Expr',
+		'crate::ast::expr::{Expr, Pattern}',
+		'std::rc::Rc',
 	]
 
 	literal_types: List[str] = []
 
+	additional_code = """
+		pub struct Field {
+			pub name: Token,
+			pub initializer: Option<Expr>,
+			pub is_static: bool,
+			pub doc: Option<Rc<str>>,
+		}
+
+		pub struct Catch {
+			pub name: Option<Token>,
+			pub body: Option<Box<Stmt>>,
+		}
+
+		pub struct Variant {
+			pub name: Token,
+			/// `None` for variants declared without parentheses
+			pub fields: Option<Vec<Token>>,
+		}
+	"""
+
 	return generate_ast(
 		'Stmt',
 		to_generate,
 		imports,
 		literal_types,
 		None,
-		None,
+		additional_code,
 	)
 
 def write_to_file(text: str, path: str) -> None: